@@ -0,0 +1,955 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::mem::swap;
+use std::str::FromStr;
+
+use chess_pgn_parser::{GameMove, Square};
+use chess_pgn_parser::Move::{BasicMove, CastleKingside, CastleQueenside};
+use chess_pgn_parser::Piece::{self, Bishop, King, Knight, Pawn, Queen, Rook};
+
+use self::Color::{Black, White};
+
+macro_rules! find_piece {
+    ($ident:ident, $piece:pat, $deltas:expr) => {
+        fn $ident(&mut self, to_x: usize, to_y: usize, color: &Color, maybe_from_x: Option<usize>, maybe_from_y: Option<usize>, check_can_move: bool) -> Option<(usize, usize)> {
+            if let (Some(from_x), Some(from_y)) = (maybe_from_x, maybe_from_y) {
+                return Some((from_x, from_y));
+            }
+            for &(dx, dy) in &$deltas {
+                let mut x = to_x as i32 + dx;
+                let mut y = to_y as i32 + dy;
+                while is_valid(x, y) {
+                    {
+                        let x = x as usize;
+                        let y = y as usize;
+                        // Any piece ends the line, whether it is the one moving or not.
+                        match self.board[y][x] {
+                            None => (),
+                            Some((square_color, $piece)) if square_color == *color => {
+                                let from_matches = maybe_from_x.map_or(true, |from_x| x == from_x) &&
+                                    maybe_from_y.map_or(true, |from_y| y == from_y);
+                                if from_matches && (!check_can_move || self.piece_can_move(x, y, to_x, to_y, color)) {
+                                    return Some((x, y));
+                                }
+                                break;
+                            },
+                            _ => break,
+                        }
+                    }
+                    x += dx;
+                    y += dy;
+                }
+            }
+            None
+        }
+    };
+}
+
+macro_rules! play {
+    ($ident:ident, $color:expr, $delta:expr) => {
+        fn $ident(&mut self, game_move: &GameMove) -> bool {
+            match game_move.move_.move_ {
+                BasicMove { ref from, is_capture, ref piece, ref promoted_to, ref to } => {
+                    let (maybe_from_x, maybe_from_y) = square_to_maybe_indexes(from);
+                    let (to_x, to_y) = square_to_indexes(to);
+                    match *piece {
+                        Bishop => {
+                            let (from_x, from_y) = match self.find_bishop(to_x, to_y, &$color, maybe_from_x, maybe_from_y, true) {
+                                Some(position) => position,
+                                None => return false,
+                            };
+                            self.board[to_y][to_x] = Some(($color, Bishop));
+                            self.board[from_y][from_x] = None;
+                        },
+                        King => {
+                            let (from_x, from_y) = match self.find_king(to_x, to_y, &$color) {
+                                Some(position) => position,
+                                None => return false,
+                            };
+                            self.board[to_y][to_x] = Some(($color, King));
+                            self.board[from_y][from_x] = None;
+                            self.move_king(&$color, to_x, to_y);
+                        },
+                        Knight => {
+                            let (from_x, from_y) = match self.find_knight(to_x, to_y, &$color, maybe_from_x, maybe_from_y) {
+                                Some(position) => position,
+                                None => return false,
+                            };
+                            self.board[to_y][to_x] = Some(($color, Knight));
+                            self.board[from_y][from_x] = None;
+                        },
+                        Pawn => {
+                            let (from_x, from_y) = match self.find_pawn(to_x, to_y, maybe_from_x, is_capture, &$color, $delta) {
+                                Some(position) => position,
+                                None => return false,
+                            };
+                            let new_piece =
+                                if let Some(piece) = *promoted_to {
+                                    piece
+                                }
+                                else {
+                                    Pawn
+                                };
+                            // En passant.
+                            if is_capture && self.board[to_y][to_x].is_none() {
+                                self.board[(to_y as i32 + $delta) as usize][to_x] = None;
+                            }
+                            self.board[to_y][to_x] = Some(($color, new_piece));
+                            self.board[from_y][from_x] = None;
+                            if (from_y as i32 - to_y as i32).abs() == 2 {
+                                self.en_passant = Some((to_x, (from_y + to_y) / 2));
+                            }
+                        },
+                        Queen => {
+                            let (from_x, from_y) = match self.find_queen(to_x, to_y, &$color, maybe_from_x, maybe_from_y, true) {
+                                Some(position) => position,
+                                None => return false,
+                            };
+                            self.board[to_y][to_x] = Some(($color, Queen));
+                            self.board[from_y][from_x] = None;
+                        },
+                        Rook => {
+                            let (from_x, from_y) = match self.find_rook(to_x, to_y, &$color, maybe_from_x, maybe_from_y, true) {
+                                Some(position) => position,
+                                None => return false,
+                            };
+                            self.board[to_y][to_x] = Some(($color, Rook));
+                            self.board[from_y][from_x] = None;
+                        },
+                    }
+                },
+                CastleKingside => {
+                    let line =
+                        match $color {
+                            Black => 0,
+                            White => 7,
+                        };
+                    self.move_king(&$color, 6, line);
+                    self.board[line][6] = Some(($color, King));
+                    self.board[line][4] = None;
+                    self.board[line][5] = Some(($color, Rook));
+                    self.board[line][7] = None;
+                },
+                CastleQueenside => {
+                    let line =
+                        match $color {
+                            Black => 0,
+                            White => 7,
+                        };
+                    self.move_king(&$color, 2, line);
+                    self.board[line][2] = Some(($color, King));
+                    self.board[line][4] = None;
+                    self.board[line][3] = Some(($color, Rook));
+                    self.board[line][0] = None;
+                },
+            }
+            true
+        }
+    };
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Color {
+    Black,
+    White,
+}
+
+impl Color {
+    /// Get the sentence saying that this side is to play.
+    pub fn to_play(&self) -> &'static str {
+        match *self {
+            Black => "Black to play",
+            White => "White to play",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ChessGame {
+    black_king: (usize, usize),
+    board: [[Option<(Color, Piece)>; 8]; 8],
+    /// Castling rights, in the FEN order: white kingside, white queenside, black kingside, black queenside.
+    castling: [bool; 4],
+    en_passant: Option<(usize, usize)>,
+    fullmove_number: u32,
+    halfmove_clock: u32,
+    turn: Color,
+    white_king: (usize, usize),
+}
+
+impl ChessGame {
+    pub fn initial() -> Self {
+        let board = [
+            [Some((Black, Rook)), Some((Black, Knight)),Some((Black, Bishop)), Some((Black, Queen)), Some((Black, King)), Some((Black, Bishop)), Some((Black, Knight)), Some((Black, Rook))],
+            [Some((Black, Pawn)), Some((Black, Pawn)), Some((Black, Pawn)), Some((Black, Pawn)), Some((Black, Pawn)), Some((Black, Pawn)), Some((Black, Pawn)), Some((Black, Pawn))],
+            [None, None, None, None, None, None, None, None],
+            [None, None, None, None, None, None, None, None],
+            [None, None, None, None, None, None, None, None],
+            [None, None, None, None, None, None, None, None],
+            [Some((White, Pawn)), Some((White, Pawn)), Some((White, Pawn)), Some((White, Pawn)), Some((White, Pawn)), Some((White, Pawn)), Some((White, Pawn)), Some((White, Pawn))],
+            [Some((White, Rook)), Some((White, Knight)), Some((White, Bishop)), Some((White, Queen)), Some((White, King)), Some((White, Bishop)), Some((White, Knight)), Some((White, Rook))],
+        ];
+        ChessGame {
+            black_king: (4, 0),
+            board: board,
+            castling: [true; 4],
+            en_passant: None,
+            fullmove_number: 1,
+            halfmove_clock: 0,
+            turn: White,
+            white_king: (4, 7),
+        }
+    }
+
+    fn find_king(&self, to_x: usize, to_y: usize, color: &Color) -> Option<(usize, usize)> {
+        let deltas = [
+            (-1, -1), (0, -1), (1, -1),
+            (-1, 0), (1, 0),
+            (-1, 1), (0, 1), (1, 1)
+        ];
+        for &(dx, dy) in &deltas {
+            let x = to_x as i32 + dx;
+            let y = to_y as i32 + dy;
+            if is_valid(x, y) {
+                let x = x as usize;
+                let y = y as usize;
+                if let Some((ref square_color, King)) = self.board[y][x] {
+                    if square_color == color {
+                        return Some((x, y));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn find_knight(&mut self, to_x: usize, to_y: usize, color: &Color, maybe_from_x: Option<usize>, maybe_from_y: Option<usize>) -> Option<(usize, usize)> {
+        if let (Some(from_x), Some(from_y)) = (maybe_from_x, maybe_from_y) {
+            return Some((from_x, from_y));
+        }
+        let deltas = [
+            (-1, -2),
+            (-2, -1),
+            (1, -2),
+            (2, -1),
+            (-1, 2),
+            (-2, 1),
+            (1, 2),
+            (2, 1),
+        ];
+
+        for &(dx, dy) in &deltas {
+            let x = to_x as i32 + dx;
+            let y = to_y as i32 + dy;
+            if is_valid(x, y) {
+                let x = x as usize;
+                let y = y as usize;
+                if self.piece_can_move(x, y, to_x, to_y, color) {
+                    if let Some((ref square_color, Knight)) = self.board[y][x] {
+                        if square_color == color {
+                            if let Some(from_x) = maybe_from_x {
+                                if x == from_x {
+                                    return Some((x, y));
+                                }
+                            }
+                            else if let Some(from_y) = maybe_from_y {
+                                if y == from_y {
+                                    return Some((x, y));
+                                }
+                            }
+                            else {
+                                return Some((x, y));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn find_pawn(&mut self, to_x: usize, to_y: usize, maybe_from_x: Option<usize>, is_capture: bool, color: &Color, delta: i32) -> Option<(usize, usize)> {
+        let index1 = to_y as i32 + delta;
+        let index2 = to_y as i32 + delta * 2;
+        if coord_valid(index1) {
+            let index1 = index1 as usize;
+            if is_capture {
+                match maybe_from_x {
+                    Some(x) => {
+                        if let Some((_, Pawn)) = self.board[index1][x] {
+                            if self.piece_can_move(x, index1, to_x, to_y, color) {
+                                return Some((x, index1));
+                            }
+                        }
+                    },
+                    None => {
+                        let x1 = to_x as i32 - 1;
+                        if coord_valid(x1) {
+                            let x1 = x1 as usize;
+                            if let Some((_, Pawn)) = self.board[index1][x1] {
+                                if self.piece_can_move(x1, index1, to_x, to_y, color) {
+                                    return Some((x1, index1));
+                                }
+                            }
+                        }
+                        let x2 = to_x as i32 + 1;
+                        if coord_valid(x2) {
+                            let x2 = x2 as usize;
+                            if let Some((_, Pawn)) = self.board[index1][x2] {
+                                if self.piece_can_move(x2, index1, to_x, to_y, color) {
+                                    return Some((x2, index1));
+                                }
+                            }
+                        }
+                    },
+                }
+            }
+            else {
+                if let Some((_, Pawn)) = self.board[index1][to_x] {
+                    if self.piece_can_move(to_x, index1, to_x, to_y, color) {
+                        return Some((to_x, index1));
+                    }
+                }
+                if coord_valid(index2) {
+                    let index2 = index2 as usize;
+                    if let Some((_, Pawn)) = self.board[index2][to_x] {
+                        if self.piece_can_move(to_x, index2, to_x, to_y, color) {
+                            return Some((to_x, index2));
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    find_piece!(find_bishop, Bishop, [
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+    ]);
+
+    find_piece!(find_queen, Queen, [
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+        (1, 0),
+        (0, 1),
+        (-1, 0),
+        (0, -1),
+    ]);
+
+    find_piece!(find_rook, Rook, [
+        (1, 0),
+        (0, 1),
+        (-1, 0),
+        (0, -1),
+    ]);
+
+    fn move_king(&mut self, color: &Color, x: usize, y: usize) {
+        if *color == White {
+            self.white_king = (x, y);
+        }
+        else {
+            self.black_king = (x, y);
+        }
+    }
+
+    /// Check that moving the piece at `x`, `y` to `to_x`, `to_y` does not leave its king in check. The piece is put
+    /// on its destination during the check, since it can stay on the line of the pin or capture the pinning piece.
+    fn piece_can_move(&mut self, x: usize, y: usize, to_x: usize, to_y: usize, color: &Color) -> bool {
+        let mut can_move = true;
+        let mut piece = None;
+        swap(&mut piece, &mut self.board[y][x]);
+        if piece.is_some() {
+            let mut captured = piece;
+            swap(&mut captured, &mut self.board[to_y][to_x]);
+            let (king_x, king_y) =
+                if *color == White {
+                    self.white_king
+                }
+                else {
+                    self.black_king
+                };
+            if self.find_bishop(king_x, king_y, &opposite(color), None, None, false).is_some() ||
+                self.find_rook(king_x, king_y, &opposite(color), None, None, false).is_some() ||
+                self.find_queen(king_x, king_y, &opposite(color), None, None, false).is_some()
+            {
+                can_move = false;
+            }
+            self.board[to_y][to_x] = captured;
+            self.board[y][x] = piece;
+        }
+        can_move
+    }
+
+    /// Play the move and return false if it is illegal in the current position.
+    pub fn play(&mut self, game_move: &GameMove) -> bool {
+        self.en_passant = None;
+        if self.turn == White {
+            if !self.play_white(game_move) {
+                return false;
+            }
+            self.turn = Black;
+        }
+        else {
+            if !self.play_black(game_move) {
+                return false;
+            }
+            self.turn = White;
+            self.fullmove_number += 1;
+        }
+        let resets_clock =
+            match game_move.move_.move_ {
+                BasicMove { piece: Pawn, .. } => true,
+                BasicMove { is_capture, .. } => is_capture,
+                CastleKingside | CastleQueenside => false,
+            };
+        if resets_clock {
+            self.halfmove_clock = 0;
+        }
+        else {
+            self.halfmove_clock += 1;
+        }
+        self.update_castling();
+        true
+    }
+
+    /// Remove the castling rights once the king or a rook has left its initial square.
+    fn update_castling(&mut self) {
+        let corners = [(7, 7, White), (0, 7, White), (7, 0, Black), (0, 0, Black)];
+        for (index, &(x, y, color)) in corners.iter().enumerate() {
+            let rook_at_home =
+                match self.board[y][x] {
+                    Some((rook_color, Rook)) => rook_color == color,
+                    _ => false,
+                };
+            let king_at_home =
+                match self.board[y][4] {
+                    Some((king_color, King)) => king_color == color,
+                    _ => false,
+                };
+            if !rook_at_home || !king_at_home {
+                self.castling[index] = false;
+            }
+        }
+    }
+
+    play!(play_black, Black, -1);
+    play!(play_white, White, 1);
+
+    /// Get the piece on the square at column `x` and row `y`, the row 0 being the 8th rank.
+    pub fn square(&self, x: usize, y: usize) -> Option<&(Color, Piece)> {
+        self.board[y][x].as_ref()
+    }
+
+    pub fn turn(&self) -> Color {
+        self.turn
+    }
+
+    /// Get the number of the next full move, starting at 1 and incremented after each move of black.
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    /// Get the position in the Forsyth-Edwards Notation.
+    pub fn fen(&self) -> String {
+        let mut rows = vec![];
+        for row in &self.board {
+            let mut string = String::new();
+            let mut empty = 0;
+            for square in row {
+                match *square {
+                    Some(ref piece) => {
+                        if empty > 0 {
+                            string += &empty.to_string();
+                            empty = 0;
+                        }
+                        string.push(piece_letter(piece));
+                    },
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                string += &empty.to_string();
+            }
+            rows.push(string);
+        }
+        let turn =
+            match self.turn {
+                Black => "b",
+                White => "w",
+            };
+        let castling: String = "KQkq".chars()
+            .zip(self.castling.iter())
+            .filter(|&(_, &allowed)| allowed)
+            .map(|(letter, _)| letter)
+            .collect();
+        let castling =
+            if castling.is_empty() {
+                "-".to_string()
+            }
+            else {
+                castling
+            };
+        let en_passant =
+            match self.en_passant {
+                Some((x, y)) => format!("{}{}", (b'a' + x as u8) as char, 8 - y),
+                None => "-".to_string(),
+            };
+        format!("{} {} {} {} {} {}", rows.join("/"), turn, castling, en_passant, self.halfmove_clock,
+            self.fullmove_number)
+    }
+
+    pub fn show(&self) -> String {
+        self.show_with(DiagramFont::MeridaUnicode, &[], Coordinates::Border, Indicator::Dot)
+    }
+
+    /// Get the AsciiDoc diagram of the position, drawn with the glyphs of the chess `font` in the theme `roles`.
+    /// Without roles, the font is the one of the blockquotes.
+    pub fn show_with(&self, font: DiagramFont, roles: &[String], coordinates: Coordinates, indicator: Indicator)
+        -> String
+    {
+        // The fonts have no glyph for the coordinates inside the squares.
+        let coordinates = coordinates != Coordinates::None;
+        let glyph = |border| {
+            match font.border(border, coordinates) {
+                Some(glyph) => format!("&#{};", glyph),
+                None => String::new(),
+            }
+        };
+        let mut string = glyph(Border::TopLeft);
+        for x in 0..8 {
+            string.push_str(&glyph(Border::Top(x)));
+        }
+        string.push_str(&glyph(Border::TopRight));
+        if !string.is_empty() {
+            string.push_str(" +\n");
+        }
+        for (y, row) in self.board.iter().enumerate() {
+            string.push_str(&glyph(Border::Left(y)));
+            for (x, square) in row.iter().enumerate() {
+                let white_square = (x + y) % 2 == 0;
+                let num = font.square(square, white_square);
+                string.push_str(&format!("&#{};", num));
+            }
+            string.push_str(&glyph(Border::Right(y)));
+            match (indicator, y, self.turn) {
+                (Indicator::Dot, 0, Black) => string.push_str("icon:circle[size=70%]"),
+                (Indicator::Dot, 7, White) => string.push_str("icon:circle-thin[size=70%]"),
+                (Indicator::Arrow, 0, Black) | (Indicator::Arrow, 7, White) =>
+                    string.push_str("icon:caret-left[size=70%]"),
+                _ => (),
+            }
+            if y < 7 {
+                string.push_str(" +\n");
+            }
+        }
+        let mut bottom = glyph(Border::BottomLeft);
+        for x in 0..8 {
+            bottom.push_str(&glyph(Border::Bottom(x)));
+        }
+        bottom.push_str(&glyph(Border::BottomRight));
+        if !bottom.is_empty() {
+            string.push_str(" +\n");
+            string.push_str(&bottom);
+        }
+        let mut diagram =
+            if roles.is_empty() {
+                string + " +"
+            }
+            else {
+                // The last hard line break would be hidden by the end of the span.
+                format!("[.{}]##{}##", roles.join("."), string)
+            };
+        if indicator == Indicator::Text {
+            if !roles.is_empty() {
+                diagram.push_str(" +");
+            }
+            diagram.push_str(&format!("\n[.side-to-move]#{}#", self.turn.to_play()));
+        }
+        diagram
+    }
+}
+
+/// Coordinates shown around a diagram.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Coordinates {
+    /// On the border of the board.
+    Border,
+    /// In the corner of the squares of the first rank and of the first file. The diagrams drawn with a font show
+    /// them on the border.
+    Inside,
+    None,
+}
+
+impl FromStr for Coordinates {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, String> {
+        match string {
+            "border" => Ok(Coordinates::Border),
+            "inside" => Ok(Coordinates::Inside),
+            "none" => Ok(Coordinates::None),
+            _ => Err(format!("unknown coordinates {}", string)),
+        }
+    }
+}
+
+/// Indicator of the side to move next to a diagram.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Indicator {
+    /// An arrow next to the rank of the side to move.
+    Arrow,
+    /// A white or black dot next to the rank of the side to move.
+    Dot,
+    None,
+    /// A sentence below the diagram, like "White to play".
+    Text,
+}
+
+impl FromStr for Indicator {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, String> {
+        match string {
+            "arrow" => Ok(Indicator::Arrow),
+            "dot" => Ok(Indicator::Dot),
+            "none" => Ok(Indicator::None),
+            "text" => Ok(Indicator::Text),
+            _ => Err(format!("unknown side to move indicator {}", string)),
+        }
+    }
+}
+
+/// Part of the border of a diagram, the rows being numbered from the 8th rank.
+enum Border {
+    TopLeft,
+    Top(usize),
+    TopRight,
+    Left(usize),
+    Right(usize),
+    BottomLeft,
+    Bottom(usize),
+    BottomRight,
+}
+
+/// Chess font of the diagrams of the PDF, each having its own glyph mapping.
+/// The font file of each is set in the theme.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiagramFont {
+    /// Chess Alpha, by Eric Bentzen.
+    Alpha,
+    /// Chess Cases, by Eric Bentzen.
+    Cases,
+    /// Chess Leipzig, by Eric Bentzen.
+    Leipzig,
+    /// Chess Merida, by Armando Hernandez Marroquin, with the mapping of the fonts of Eric Bentzen.
+    Merida,
+    /// Chess Merida Unicode, whose light squares use the Unicode chess symbols.
+    MeridaUnicode,
+}
+
+impl FromStr for DiagramFont {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, String> {
+        match string {
+            "alpha" => Ok(DiagramFont::Alpha),
+            "cases" => Ok(DiagramFont::Cases),
+            "leipzig" => Ok(DiagramFont::Leipzig),
+            "merida" => Ok(DiagramFont::Merida),
+            "merida-unicode" => Ok(DiagramFont::MeridaUnicode),
+            _ => Err(format!("unknown diagram font {}", string)),
+        }
+    }
+}
+
+impl DiagramFont {
+    /// Get the theme role of the text drawn with this font.
+    pub fn role(&self) -> &'static str {
+        match *self {
+            DiagramFont::Alpha => "chess-alpha",
+            DiagramFont::Cases => "chess-cases",
+            DiagramFont::Leipzig => "chess-leipzig",
+            DiagramFont::Merida => "chess-merida",
+            DiagramFont::MeridaUnicode => "chess-merida-unicode",
+        }
+    }
+
+    /// Get the glyph of a part of the border, `None` when the font cannot draw it.
+    /// The fonts of Eric Bentzen have no coordinates, while Merida Unicode has only a border with coordinates.
+    fn border(&self, border: Border, coordinates: bool) -> Option<u32> {
+        match *self {
+            DiagramFont::MeridaUnicode if !coordinates => None,
+            DiagramFont::MeridaUnicode => {
+                let glyph =
+                    match border {
+                        Border::TopLeft => 0xE308,
+                        Border::Top(x) => 0xE328 + x as u32,
+                        Border::TopRight => 0xE309,
+                        Border::Left(y) => 0xE317 - y as u32,
+                        Border::Right(y) => 0xE327 - y as u32,
+                        Border::BottomLeft => 0xE30A,
+                        Border::Bottom(x) => 0xE318 + x as u32,
+                        Border::BottomRight => 0xE30B,
+                    };
+                Some(glyph)
+            },
+            DiagramFont::Alpha | DiagramFont::Cases | DiagramFont::Leipzig | DiagramFont::Merida => {
+                let glyph =
+                    match border {
+                        Border::TopLeft => '1',
+                        Border::Top(_) => '2',
+                        Border::TopRight => '3',
+                        Border::Left(_) => '4',
+                        Border::Right(_) => '5',
+                        Border::BottomLeft => '7',
+                        Border::Bottom(_) => '8',
+                        Border::BottomRight => '9',
+                    };
+                Some(glyph as u32)
+            },
+        }
+    }
+
+    fn square(&self, square: &Option<(Color, Piece)>, white_square: bool) -> u32 {
+        match *self {
+            DiagramFont::MeridaUnicode => merida_unicode_square(square, white_square),
+            DiagramFont::Alpha | DiagramFont::Cases | DiagramFont::Leipzig | DiagramFont::Merida =>
+                bentzen_square(square, white_square) as u32,
+        }
+    }
+}
+
+fn merida_unicode_square(square: &Option<(Color, Piece)>, white_square: bool) -> u32 {
+    match *square {
+        Some(ref square) => {
+            if white_square {
+                piece_symbol(square) as u32
+            }
+            else {
+                match *square {
+                    (Black, Bishop) => 0xE15D,
+                    (Black, King) => 0xE15A,
+                    (Black, Knight) => 0xE15E,
+                    (Black, Pawn) => 0xE15F,
+                    (Black, Queen) => 0xE15B,
+                    (Black, Rook) => 0xE15C,
+                    (White, Bishop) => 0xE157,
+                    (White, King) => 0xE154,
+                    (White, Knight) => 0xE158,
+                    (White, Pawn) => 0xE159,
+                    (White, Queen) => 0xE155,
+                    (White, Rook) => 0xE156,
+                }
+            }
+        },
+        None => if white_square {
+                    0xA0
+                }
+                else {
+                    0xE100
+                },
+    }
+}
+
+/// Get the glyph of a square in the fonts of Eric Bentzen: lowercase letters on light squares, uppercase letters on
+/// dark squares.
+fn bentzen_square(square: &Option<(Color, Piece)>, white_square: bool) -> char {
+    let glyph =
+        match *square {
+            Some((White, Bishop)) => 'b',
+            Some((White, King)) => 'k',
+            Some((White, Knight)) => 'n',
+            Some((White, Pawn)) => 'p',
+            Some((White, Queen)) => 'q',
+            Some((White, Rook)) => 'r',
+            Some((Black, Bishop)) => 'v',
+            Some((Black, King)) => 'l',
+            Some((Black, Knight)) => 'm',
+            Some((Black, Pawn)) => 'o',
+            Some((Black, Queen)) => 'w',
+            Some((Black, Rook)) => 't',
+            None if white_square => return ' ',
+            None => return '+',
+        };
+    if white_square {
+        glyph
+    }
+    else {
+        glyph.to_ascii_uppercase()
+    }
+}
+
+/// Get the letter of a piece in the Forsyth-Edwards Notation.
+fn piece_letter(piece: &(Color, Piece)) -> char {
+    let letter =
+        match piece.1 {
+            Bishop => 'b',
+            King => 'k',
+            Knight => 'n',
+            Pawn => 'p',
+            Queen => 'q',
+            Rook => 'r',
+        };
+    if piece.0 == White {
+        letter.to_ascii_uppercase()
+    }
+    else {
+        letter
+    }
+}
+
+/// Get the Unicode chess symbol of a piece.
+pub fn piece_symbol(piece: &(Color, Piece)) -> char {
+    match *piece {
+        (Black, Bishop) => '♝',
+        (Black, King) => '♚',
+        (Black, Knight) => '♞',
+        (Black, Pawn) => '♟',
+        (Black, Queen) => '♛',
+        (Black, Rook) => '♜',
+        (White, Bishop) => '♗',
+        (White, King) => '♔',
+        (White, Knight) => '♘',
+        (White, Pawn) => '♙',
+        (White, Queen) => '♕',
+        (White, Rook) => '♖',
+    }
+}
+
+/// Get the column and the row of a square named like `e4`, the row 0 being the 8th rank.
+pub fn parse_square(name: &str) -> Option<(usize, usize)> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(column @ 'a' ... 'h'), Some(line @ '1' ... '8'), None) =>
+            Some((column as usize - 'a' as usize, 8 - (line as usize - '0' as usize))),
+        _ => None,
+    }
+}
+
+fn square_to_maybe_indexes(square: &Square) -> (Option<usize>, Option<usize>) {
+    let string = format!("{:?}", square).to_lowercase();
+    let mut chars = string.chars();
+    let column = chars.next().unwrap();
+    let line = chars.next().unwrap();
+    let x =
+        match column {
+            'a' => Some(0),
+            'b' => Some(1),
+            'c' => Some(2),
+            'd' => Some(3),
+            'e' => Some(4),
+            'f' => Some(5),
+            'g' => Some(6),
+            'h' => Some(7),
+            'x' => None,
+            _ => unreachable!(),
+        };
+    let y =
+        if line == 'x' {
+            None
+        }
+        else {
+            Some(8 - (line as usize - '0' as usize))
+
+        };
+    (x, y)
+}
+
+fn square_to_indexes(square: &Square) -> (usize, usize) {
+    let string = format!("{:?}", square).to_lowercase();
+    let mut chars = string.chars();
+    let column = chars.next().unwrap();
+    let line = chars.next().unwrap();
+    let x =
+        match column {
+            'a' => 0,
+            'b' => 1,
+            'c' => 2,
+            'd' => 3,
+            'e' => 4,
+            'f' => 5,
+            'g' => 6,
+            'h' => 7,
+            _ => unreachable!(),
+        };
+    let y = 8 - (line as usize - '0' as usize);
+    (x, y)
+}
+
+fn coord_valid(x: i32) -> bool {
+    x >= 0 && x < 8
+}
+
+fn is_valid(x: i32, y: i32) -> bool {
+    coord_valid(x) && coord_valid(y)
+}
+
+fn opposite(color: &Color) -> Color {
+    match *color {
+        Black => White,
+        White => Black,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chess_pgn_parser::read_games;
+
+    use super::ChessGame;
+
+    /// Get the position after the moves of the PGN `movetext`.
+    fn position(movetext: &str) -> ChessGame {
+        let games = read_games(&format!("[Event \"?\"]\n\n{} *\n", movetext)).unwrap();
+        let mut position = ChessGame::initial();
+        for game_move in &games[0].moves {
+            assert!(position.play(game_move));
+        }
+        position
+    }
+
+    #[test]
+    fn fen() {
+        assert_eq!(ChessGame::initial().fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        assert_eq!(position("1. e4").fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        assert_eq!(position("1. e4 c5 2. Nf3").fen(),
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+        assert_eq!(position("1. e4 e5 2. Nf3 Nc6 3. Bc4 Bc5 4. O-O Ke7").fen(),
+            "r1bq2nr/ppppkppp/2n5/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQ1RK1 w - - 6 5");
+        assert_eq!(position("1. d4 Nf6 2. Nf3 Rg8 3. Bf4 Rh8").fen(),
+            "rnbqkb1r/pppppppp/5n2/8/3P1B2/5N2/PPP1PPPP/RN1QKB1R w KQq - 5 4");
+    }
+
+    #[test]
+    fn pinned_pieces() {
+        // The pawn captures the bishop pinning it.
+        assert_eq!(position("1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 4. Bxc6 dxc6").fen(),
+            "r1bqkbnr/1pp2ppp/p1p5/4p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 0 5");
+        // The queen stays on the line of the pin.
+        assert_eq!(position("1. e4 d5 2. exd5 Qxd5 3. Qe2 Qe5 4. Qe3").fen(),
+            "rnb1kbnr/ppp1pppp/8/4q3/8/4Q3/PPPP1PPP/RNB1KBNR b KQkq - 3 4");
+    }
+}
@@ -0,0 +1,924 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use chess_pgn_parser::{Game, read_games};
+use tempdir::TempDir;
+
+use board::{Coordinates, DiagramFont, Indicator};
+use error::{Error, Result};
+use page::Page;
+use render::{MOVES_TO_SHOW, Note, Sheet, asciidoc, epub, html, latex, markdown, svg, text};
+use render::header::Header;
+use render::pdf::run_asciidoctor;
+use render::svg::SvgOptions;
+use template::Template;
+use theme::{DEFAULT_THEME, Theme};
+
+/// Widest diagram accepted, in points: 20 inches, wider than the largest named page size.
+const MAX_DIAGRAM_SIZE: f32 = 1440.0;
+
+/// Output format of a document.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// The AsciiDoc document given to asciidoctor-pdf.
+    Asciidoc,
+    Epub,
+    Html,
+    Latex,
+    Markdown,
+    Pdf,
+    /// The diagram of each game, as an SVG image.
+    Svg,
+    Text,
+}
+
+impl Format {
+    /// Get the usual extension of the files of this format.
+    pub fn extension(&self) -> &'static str {
+        match *self {
+            Format::Asciidoc => "adoc",
+            Format::Epub => "epub",
+            Format::Html => "html",
+            Format::Latex => "tex",
+            Format::Markdown => "md",
+            Format::Pdf => "pdf",
+            Format::Svg => "svg",
+            Format::Text => "txt",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(string: &str) -> ::std::result::Result<Self, String> {
+        match string {
+            "adoc" => Ok(Format::Asciidoc),
+            "epub" => Ok(Format::Epub),
+            "html" => Ok(Format::Html),
+            "latex" => Ok(Format::Latex),
+            "markdown" => Ok(Format::Markdown),
+            "pdf" => Ok(Format::Pdf),
+            "svg" => Ok(Format::Svg),
+            "text" => Ok(Format::Text),
+            _ => Err(format!("unknown format {}", string)),
+        }
+    }
+}
+
+/// How the diagrams are drawn in the PDF.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiagramStyle {
+    /// With the glyphs of a chess font.
+    Font,
+    /// As SVG images.
+    Svg,
+}
+
+impl FromStr for DiagramStyle {
+    type Err = String;
+
+    fn from_str(string: &str) -> ::std::result::Result<Self, String> {
+        match string {
+            "font" => Ok(DiagramStyle::Font),
+            "svg" => Ok(DiagramStyle::Svg),
+            _ => Err(format!("unknown diagram style {}", string)),
+        }
+    }
+}
+
+/// Width of a diagram, including its border.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiagramSize {
+    Small,
+    Medium,
+    Large,
+    /// Width in points.
+    Points(f32),
+}
+
+impl DiagramSize {
+    /// Get the width of the diagram, in points.
+    pub fn points(&self) -> f32 {
+        match *self {
+            DiagramSize::Small => 160.0,
+            DiagramSize::Medium => 240.0,
+            DiagramSize::Large => 320.0,
+            DiagramSize::Points(points) => points,
+        }
+    }
+
+    /// Get the theme role of the diagrams drawn with a font at this size.
+    pub fn role(&self) -> String {
+        match *self {
+            DiagramSize::Small => "diagram-small".to_string(),
+            DiagramSize::Medium => "diagram-medium".to_string(),
+            DiagramSize::Large => "diagram-large".to_string(),
+            DiagramSize::Points(points) => format!("diagram-{}pt", points.round()),
+        }
+    }
+}
+
+impl FromStr for DiagramSize {
+    type Err = String;
+
+    /// Parse a named size or a width in points.
+    fn from_str(string: &str) -> ::std::result::Result<Self, String> {
+        match string {
+            "small" => Ok(DiagramSize::Small),
+            "medium" => Ok(DiagramSize::Medium),
+            "large" => Ok(DiagramSize::Large),
+            _ => {
+                match string.trim_right_matches("pt").parse::<f32>() {
+                    Ok(points) if points.is_finite() && points > 0.0 && points <= MAX_DIAGRAM_SIZE =>
+                        Ok(DiagramSize::Points(points)),
+                    _ => Err(format!("invalid diagram size {}", string)),
+                }
+            },
+        }
+    }
+}
+
+/// Layout of the move text and the foot-notes in the PDF.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Layout {
+    OneColumn,
+    /// Two columns, as in chess books; the diagram and the variation table keep the full width.
+    TwoColumns,
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(string: &str) -> ::std::result::Result<Self, String> {
+        match string {
+            "one-column" => Ok(Layout::OneColumn),
+            "two-columns" => Ok(Layout::TwoColumns),
+            _ => Err(format!("unknown layout {}", string)),
+        }
+    }
+}
+
+/// Kind of the games of a document, which decides the title and the sections of their pages.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    /// Use the `SheetMode` tag of each game, or guess the mode from its tags, see `Mode::of()`.
+    Auto,
+    /// A played game: the players are the title, followed by the header.
+    Game,
+    /// A line of a repertoire, whose `White` and `Black` tags are the name of the opening and of the variation.
+    Repertoire,
+}
+
+impl Mode {
+    /// Get the mode of the `game`.
+    /// Unless set by the `SheetMode` tag, a game with a result or the Elo of a player is a played game.
+    pub fn of(self, game: &Game) -> Mode {
+        if self != Mode::Auto {
+            return self;
+        }
+        let tag = |name: &str| {
+            game.tags.iter()
+                .find(|&&(ref tag, _)| tag == name)
+                .map(|&(_, ref value)| value.trim())
+        };
+        if let Some(Ok(mode)) = tag("SheetMode").map(str::parse::<Mode>) {
+            if mode != Mode::Auto {
+                return mode;
+            }
+        }
+        let is_known = |value: Option<&str>, unknown: &[&str]| value.map_or(false, |value| !unknown.contains(&value));
+        if is_known(tag("Result"), &["", "*"]) || is_known(tag("WhiteElo"), &["", "-", "?", "0"]) ||
+            is_known(tag("BlackElo"), &["", "-", "?", "0"])
+        {
+            Mode::Game
+        }
+        else {
+            Mode::Repertoire
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(string: &str) -> ::std::result::Result<Self, String> {
+        match string {
+            "auto" => Ok(Mode::Auto),
+            "game" => Ok(Mode::Game),
+            "repertoire" => Ok(Mode::Repertoire),
+            _ => Err(format!("unknown mode {}", string)),
+        }
+    }
+}
+
+/// Options controlling how a document is rendered.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Author of the PDF, written in its metadata and on its cover page.
+    pub author: Option<String>,
+    /// Where to show the coordinates of the diagrams drawn with a font.
+    pub coordinates: Coordinates,
+    /// Image shown on the first page of the PDF.
+    pub cover: Option<PathBuf>,
+    pub diagram: DiagramStyle,
+    /// Chess font of the diagrams drawn with a font.
+    pub diagram_font: DiagramFont,
+    /// Size of the diagram of the PDF.
+    pub diagram_size: DiagramSize,
+    pub format: Format,
+    /// Tag grouping the games of the PDF under a heading with its value, like `ECO`.
+    pub group_by: Option<String>,
+    /// Show a header with the players, the event and the other tags of the games.
+    pub header: bool,
+    /// Add an index of the openings and of the players at the end of the PDF.
+    pub index: bool,
+    /// How to show the side to move next to the diagrams drawn with a font.
+    pub indicator: Indicator,
+    /// Show the comments of the moves past the variation table between parentheses, instead of in foot-notes.
+    pub inline_comments: bool,
+    /// Keywords of the PDF metadata, separated by commas.
+    pub keywords: Option<String>,
+    pub layout: Layout,
+    /// Link the foot-note numbers of the moves of the PDF to their foot-notes, and back. They are always linked in
+    /// HTML.
+    pub link_notes: bool,
+    pub mode: Mode,
+    /// Size of the diagrams of the foot-notes asked with `[%diagram]`.
+    pub note_diagram_size: DiagramSize,
+    /// Page geometry of the PDF, overriding the theme.
+    pub page: Page,
+    /// Content of the footer of the pages of the PDF, see `running_content_settings()`.
+    pub running_footer: Option<String>,
+    /// Content of the header of the pages of the PDF.
+    pub running_header: Option<String>,
+    /// Subtitle shown on the cover page of the PDF, and subject of its metadata.
+    pub subtitle: Option<String>,
+    /// Options of the SVG diagrams.
+    pub svg: SvgOptions,
+    /// AsciiDoc template of the pages of the PDF, the built-in one when `None`.
+    pub template: Option<PathBuf>,
+    /// Name of the asciidoctor-pdf theme, or path to its file, see `Theme::find()`.
+    pub theme: String,
+    /// Title of the document, instead of the one of the game or the name of the file. It adds a cover page to the
+    /// PDF.
+    pub title: Option<String>,
+    /// Add a table of contents to the PDF, and the games to its outline.
+    pub toc: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            author: None,
+            coordinates: Coordinates::Border,
+            cover: None,
+            diagram: DiagramStyle::Font,
+            diagram_font: DiagramFont::MeridaUnicode,
+            diagram_size: DiagramSize::Medium,
+            format: Format::Pdf,
+            group_by: None,
+            header: false,
+            index: false,
+            indicator: Indicator::Dot,
+            inline_comments: false,
+            keywords: None,
+            layout: Layout::OneColumn,
+            link_notes: false,
+            mode: Mode::Auto,
+            note_diagram_size: DiagramSize::Small,
+            page: Page::default(),
+            running_footer: None,
+            running_header: None,
+            subtitle: None,
+            svg: SvgOptions::default(),
+            template: None,
+            theme: DEFAULT_THEME.to_string(),
+            title: None,
+            toc: false,
+        }
+    }
+}
+
+/// A document showing one or more games.
+pub struct Document {
+    file: Option<PathBuf>,
+    games: Vec<Game>,
+    options: Options,
+}
+
+impl Document {
+    pub fn new(games: Vec<Game>, options: Options) -> Self {
+        Document {
+            file: None,
+            games: games,
+            options: options,
+        }
+    }
+
+    /// Read the games of a PGN file.
+    pub fn open<P: AsRef<Path>>(file: P, options: Options) -> Result<Self> {
+        let file = file.as_ref();
+        let games = try!(read_pgn_games(file));
+        Ok(Document {
+            file: Some(file.to_path_buf()),
+            games: games,
+            options: options,
+        })
+    }
+
+    pub fn games(&self) -> &[Game] {
+        &self.games
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Get the content of each game.
+    pub fn sheets(&self) -> Result<Vec<Sheet>> {
+        // The page options only apply to the PDF.
+        let columns =
+            match self.options.format {
+                Format::Asciidoc | Format::Pdf => self.options.page.columns(),
+                _ => MOVES_TO_SHOW,
+            };
+        let mut sheets = vec![];
+        for (index, game) in self.games.iter().enumerate() {
+            let mut sheet = try!(Sheet::with_columns(game, columns, self.options.inline_comments)
+                .map_err(|error| error.in_game(self.file.as_ref().map(|file| file.as_path()), index + 1)));
+            match self.options.mode.of(game) {
+                Mode::Game => {
+                    sheet.played = true;
+                    if let Some(mut header) = Header::new(&game.tags) {
+                        // The players are the title instead of the first line of the header.
+                        if let Some(players) = header.take_players() {
+                            sheet.title = players;
+                        }
+                        if !header.lines().is_empty() {
+                            sheet.header = Some(header);
+                        }
+                    }
+                },
+                Mode::Auto | Mode::Repertoire => {
+                    if self.options.header {
+                        sheet.header = Header::new(&game.tags);
+                    }
+                },
+            }
+            sheets.push(sheet);
+        }
+        Ok(sheets)
+    }
+
+    /// Render the document to AsciiDoc, with a page per game.
+    /// With SVG diagrams, the images are expected in the same directory, see `to_svg()`. The built-in theme and the
+    /// extension with the settings of the options are written in `theme_dir`, which must be dedicated to them.
+    pub fn to_asciidoc<P: AsRef<Path>>(&self, theme_dir: P) -> Result<String> {
+        let sheets = try!(self.sheets());
+        let theme = try!(self.install_theme(theme_dir.as_ref()));
+        self.asciidoc(&sheets, &theme)
+    }
+
+    /// Render the document to an EPUB book, with a chapter per game.
+    pub fn to_epub(&self) -> Result<Vec<u8>> {
+        let sheets = try!(self.sheets());
+        epub::render(&sheets, &self.title(&sheets), &self.options.svg)
+    }
+
+    /// Render the document to a standalone HTML page.
+    pub fn to_html(&self) -> Result<String> {
+        let sheets = try!(self.sheets());
+        Ok(html::render(&sheets, &self.title(&sheets), &self.options.svg))
+    }
+
+    /// Render the document to a standalone LaTeX document.
+    pub fn to_latex(&self) -> Result<String> {
+        let sheets = try!(self.sheets());
+        Ok(latex::render(&sheets, &self.title(&sheets)))
+    }
+
+    /// Render the document to Markdown.
+    pub fn to_markdown(&self) -> Result<String> {
+        let sheets = try!(self.sheets());
+        Ok(markdown::render(&sheets))
+    }
+
+    /// Render the document to plain text, with Unicode diagrams.
+    pub fn to_text(&self) -> Result<String> {
+        let sheets = try!(self.sheets());
+        Ok(text::render(&sheets))
+    }
+
+    /// Render the diagram of each game to an SVG image.
+    pub fn to_svg(&self) -> Result<Vec<String>> {
+        let sheets = try!(self.sheets());
+        Ok(sheets.iter()
+            .map(|sheet| svg::render(&sheet.position, &sheet.highlights, &sheet.arrows, &self.options.svg))
+            .collect())
+    }
+
+    /// Render the document to the file `output`, in the format chosen in the options.
+    /// Return the warnings of the backend, only asciidoctor-pdf having some.
+    pub fn write<P: AsRef<Path>>(&self, output: P) -> Result<Vec<String>> {
+        let output = output.as_ref();
+        match self.options.format {
+            Format::Asciidoc => try!(self.write_asciidoc(output)),
+            Format::Epub => {
+                let epub = try!(self.to_epub());
+                try!(write_file(output, &epub));
+            },
+            Format::Html => {
+                let html = try!(self.to_html());
+                try!(write_file(output, html.as_bytes()));
+            },
+            Format::Latex => {
+                let latex = try!(self.to_latex());
+                try!(write_file(output, latex.as_bytes()));
+            },
+            Format::Markdown => {
+                let markdown = try!(self.to_markdown());
+                try!(write_file(output, markdown.as_bytes()));
+            },
+            Format::Pdf => return self.write_pdf(output),
+            Format::Svg => {
+                let diagrams = try!(self.to_svg());
+                if diagrams.len() == 1 {
+                    try!(write_file(output, diagrams[0].as_bytes()));
+                }
+                else {
+                    for (index, diagram) in diagrams.iter().enumerate() {
+                        try!(write_file(&numbered_path(output, index + 1), diagram.as_bytes()));
+                    }
+                }
+            },
+            Format::Text => {
+                let text = try!(self.to_text());
+                try!(write_file(output, text.as_bytes()));
+            },
+        }
+        Ok(vec![])
+    }
+
+    /// Render the document to the AsciiDoc file `output`.
+    /// With SVG diagrams, the images are written in the same directory. The built-in theme and the extension with the
+    /// settings of the options are written in the directory `<output>.theme`.
+    pub fn write_asciidoc<P: AsRef<Path>>(&self, output: P) -> Result<()> {
+        let output = output.as_ref();
+        let sheets = try!(self.sheets());
+        let directory = output.parent().unwrap_or(Path::new("."));
+        if self.options.diagram == DiagramStyle::Svg {
+            try!(self.write_diagrams(directory, &sheets));
+        }
+        let file_name = output.file_name().unwrap_or("document".as_ref()).to_string_lossy().into_owned();
+        let theme = try!(self.install_theme(&directory.join(format!("{}.theme", file_name))));
+        let asciidoc = try!(self.asciidoc(&sheets, &theme));
+        write_file(output, asciidoc.as_bytes())
+    }
+
+    /// Render the document to the PDF file `output`, and return the warnings of asciidoctor-pdf.
+    pub fn write_pdf<P: AsRef<Path>>(&self, output: P) -> Result<Vec<String>> {
+        let sheets = try!(self.sheets());
+        let tempdir = try!(TempDir::new("pgn2pdf"));
+        if self.options.diagram == DiagramStyle::Svg {
+            try!(self.write_diagrams(tempdir.path(), &sheets));
+        }
+        let theme = try!(self.install_theme(tempdir.path()));
+        let input = tempdir.path().join(self.asciidoc_file_name());
+        let asciidoc = try!(self.asciidoc(&sheets, &theme));
+        try!(write_file(&input, asciidoc.as_bytes()));
+        run_asciidoctor(&input, output.as_ref())
+    }
+
+    /// Render the document to PDF, ignoring the warnings of asciidoctor-pdf.
+    pub fn to_pdf(&self) -> Result<Vec<u8>> {
+        let tempdir = try!(TempDir::new("pgn2pdf"));
+        let output = tempdir.path().join("output.pdf");
+        try!(self.write_pdf(&output));
+        let mut file = try!(File::open(&output).map_err(|error| Error::io(&output, error)));
+        let mut pdf = vec![];
+        try!(file.read_to_end(&mut pdf).map_err(|error| Error::io(&output, error)));
+        Ok(pdf)
+    }
+
+    fn asciidoc(&self, sheets: &[Sheet], theme: &Theme) -> Result<String> {
+        // The document is not rendered from the current directory, so its files must be found from anywhere.
+        let theme = Theme {
+            dir: Some(try!(absolute(&try!(theme.dir())))),
+            name: theme.name.clone(),
+        };
+        let template =
+            match self.options.template {
+                Some(ref path) => try!(Template::open(try!(absolute(path)))),
+                None => Template::builtin(),
+            };
+        let mut order: Vec<_> = (0 .. sheets.len()).collect();
+        if let Some(ref tag) = self.options.group_by {
+            // The sort is stable, so the games of a group keep their order.
+            order.sort_by_key(|&index| group_name(&sheets[index], tag));
+        }
+        let mut pages = vec![];
+        let mut previous_group = None;
+        for index in order {
+            let sheet = &sheets[index];
+            let group = self.options.group_by.as_ref().map(|tag| group_name(sheet, tag));
+            let heading =
+                if group.is_some() && group != previous_group {
+                    group.clone()
+                }
+                else {
+                    None
+                };
+            pages.push(try!(asciidoc::render(sheet, index + 1, heading.as_ref().map(String::as_str), &template,
+                &theme, &self.options)));
+            previous_group = group;
+        }
+        let mut document = try!(self.document_attributes(&theme));
+        document += "\n";
+        document += &pages.join("\n<<<\n\n");
+        if self.options.index {
+            document += "\n<<<\n\n[index]\n== Index\n";
+        }
+        Ok(document)
+    }
+
+    /// Get the header of the AsciiDoc document, which precedes the pages of the games: the title and the metadata,
+    /// that asciidoctor-pdf shows on the cover page and writes in the PDF, the `theme` and the page geometry, and the
+    /// table of contents and the doctype needed by the index and the running content of the grouped games.
+    fn document_attributes(&self, theme: &Theme) -> Result<String> {
+        let options = &self.options;
+        let mut attributes = String::new();
+        if let Some(ref title) = options.title {
+            match options.subtitle {
+                Some(ref subtitle) => attributes += &format!("= {}: {}\n", title, subtitle),
+                None => attributes += &format!("= {}\n", title),
+            }
+            attributes += ":title-page:\n";
+        }
+        if let Some(ref author) = options.author {
+            attributes += &format!(":author: {}\n", author);
+        }
+        if let Some(ref subtitle) = options.subtitle {
+            attributes += &format!(":subject: {}\n", subtitle);
+        }
+        if let Some(ref keywords) = options.keywords {
+            attributes += &format!(":keywords: {}\n", keywords);
+        }
+        if let Some(ref cover) = options.cover {
+            attributes += &format!(":front-cover-image: image:{}[]\n", try!(absolute(cover)).display());
+        }
+        if options.toc {
+            // The game titles are level 2 sections below the level 1 sections of the groups, or level 1 sections.
+            attributes += ":toc:\n:toclevels: 2\n:outlinelevels: 2\n";
+        }
+        if options.index || options.group_by.is_some() {
+            // asciidoctor-pdf only renders the index section of a book, and the groups must be its chapters so that the
+            // section-title attribute of the running content is the title of the game, not the one of its group.
+            attributes += ":doctype: book\n";
+        }
+        attributes += ":icons: font\n";
+        if options.running_footer.is_none() {
+            attributes += ":nofooter:\n";
+        }
+        attributes += &format!(":pdf-stylesdir: {}\n:pdf-style: {}\n", try!(theme.dir()).display(), theme.name);
+        attributes += &options.page.attributes();
+        Ok(attributes)
+    }
+
+    /// Write the SVG diagrams referenced by the AsciiDoc document in `directory`.
+    fn write_diagrams(&self, directory: &Path, sheets: &[Sheet]) -> Result<()> {
+        for (index, sheet) in sheets.iter().enumerate() {
+            let diagram = svg::render(&sheet.position, &sheet.highlights, &sheet.arrows, &self.options.svg);
+            try!(write_file(&directory.join(asciidoc::diagram_file_name(index + 1)), diagram.as_bytes()));
+            for (number, note) in sheet.notes.iter().enumerate() {
+                if let Note::Diagram(_, ref position) = *note {
+                    let diagram = svg::render(position, &[], &[], &self.options.svg);
+                    let file_name = asciidoc::note_diagram_file_name(index + 1, number + 1);
+                    try!(write_file(&directory.join(file_name), diagram.as_bytes()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the theme and write in `directory`, dedicated to it, the files it needs: the built-in theme and the
+    /// extension with the settings of the options. Return the theme to use.
+    fn install_theme(&self, directory: &Path) -> Result<Theme> {
+        let theme = try!(Theme::find(&self.options.theme));
+        if theme.dir.is_none() && self.options.diagram == DiagramStyle::Font &&
+            self.options.diagram_font != DiagramFont::MeridaUnicode
+        {
+            return Err(Error::Theme {
+                name: theme.name,
+                message: format!("the built-in theme has no diagram font {}, use a theme adding it",
+                    self.options.diagram_font.role()),
+            });
+        }
+        let theme_dir = try!(theme.install(directory));
+        let settings = self.theme_settings(theme.dir.is_none());
+        if settings.is_empty() {
+            return Ok(Theme {
+                dir: Some(theme_dir),
+                name: theme.name,
+            });
+        }
+        theme.extend(&try!(absolute(&theme_dir)), directory, &settings)
+    }
+
+    /// Get the theme settings needed by the options which cannot be in a theme, like the diagram sizes in points.
+    /// The headings of the `builtin` theme are shifted one level down when the games are grouped.
+    fn theme_settings(&self, builtin: bool) -> String {
+        let mut roles = vec![];
+        if self.options.diagram == DiagramStyle::Font {
+            for size in &[self.options.diagram_size, self.options.note_diagram_size] {
+                // The role is named after the rounded size, so two sizes rounded alike share it.
+                if let DiagramSize::Points(points) = *size {
+                    let role = (size.role(), points.round() / 10.0);
+                    if !roles.contains(&role) {
+                        roles.push(role);
+                    }
+                }
+            }
+        }
+        let mut settings = String::new();
+        if !roles.is_empty() {
+            settings += "role:\n";
+            for (role, font_size) in roles {
+                settings += &format!("  {}:\n    font_size: {}\n", role, font_size);
+            }
+        }
+        if builtin && self.options.group_by.is_some() {
+            settings += "heading:\n  h2:\n    font_size: round($base_font_size * 1.5)\n";
+            settings += "  h3:\n    font_size: $base_font_size_large\n";
+        }
+        // The games are chapters of the book of the index when they are not grouped, sections of the chapters of their
+        // groups when they are, and sections of an article otherwise.
+        let game =
+            if self.options.index && self.options.group_by.is_none() {
+                "{chapter-title}"
+            }
+            else {
+                "{section-title}"
+            };
+        if let Some(ref content) = self.options.running_header {
+            settings += &running_content_settings("header", content, game);
+        }
+        if let Some(ref content) = self.options.running_footer {
+            settings += &running_content_settings("footer", content, game);
+        }
+        settings
+    }
+
+    /// Get the title of the document: the one of the options, the title of the game if there is only one, the file name
+    /// otherwise.
+    fn title(&self, sheets: &[Sheet]) -> String {
+        if let Some(ref title) = self.options.title {
+            return title.clone();
+        }
+        if sheets.len() == 1 {
+            return sheets[0].title.clone();
+        }
+        match self.file {
+            Some(ref file) => file.file_stem().unwrap_or("".as_ref()).to_string_lossy().into_owned(),
+            None => String::new(),
+        }
+    }
+
+    fn asciidoc_file_name(&self) -> PathBuf {
+        let mut name =
+            match self.file {
+                Some(ref file) => PathBuf::from(file.file_name().unwrap_or("document".as_ref())),
+                None => PathBuf::from("document"),
+            };
+        name.set_extension("adoc");
+        name
+    }
+}
+
+/// Get the theme settings of the running header or footer, `name`, showing the `content` on every page.
+/// The content has one, two or three parts separated by `|`, aligned to the center, to the sides or to the left, the
+/// center and the right. It can contain `{page}`, `{pages}`, `{title}`, `{game}` and `{date}`, `{game}` being replaced
+/// by `game`, the asciidoctor-pdf attribute holding the title of the current game: `{chapter-title}` or
+/// `{section-title}`.
+fn running_content_settings(name: &str, content: &str, game: &str) -> String {
+    let content = content
+        .replace("{page}", "{page-number}")
+        .replace("{pages}", "{page-count}")
+        .replace("{title}", "{document-title}")
+        .replace("{game}", game)
+        .replace("{date}", "{localdate}");
+    let parts: Vec<_> = content.split('|').map(str::trim).collect();
+    let positions: &[&str] =
+        match parts.len() {
+            1 => &["center"],
+            2 => &["left", "right"],
+            _ => &["left", "center", "right"],
+        };
+    let mut columns = String::new();
+    for (position, part) in positions.iter().zip(&parts) {
+        if !part.is_empty() {
+            // The content is a single-quoted YAML string.
+            columns += &format!("    {}:\n      content: '{}'\n", position, part.replace('\'', "''"));
+        }
+    }
+    format!("{}:\n  height: 0.5in\n  recto:\n{}  verso:\n{}", name, columns, columns)
+}
+
+/// Get the name of the group of the game of `sheet`: the value of its tag `tag`.
+fn group_name(sheet: &Sheet, tag: &str) -> String {
+    sheet.tags.iter()
+        .find(|&&(ref name, _)| name == tag)
+        .map(|&(_, ref value)| value.trim())
+        .and_then(|value|
+            if value.is_empty() || value == "?" {
+                None
+            }
+            else {
+                Some(value.to_string())
+            }
+        )
+        .unwrap_or_else(|| format!("Unknown {}", tag))
+}
+
+/// Get the `path` from the current directory, when it is relative.
+pub fn absolute(path: &Path) -> Result<PathBuf> {
+    if path.is_relative() {
+        Ok(try!(env::current_dir()).join(path))
+    }
+    else {
+        Ok(path.to_path_buf())
+    }
+}
+
+fn read_pgn_games(input: &Path) -> Result<Vec<Game>> {
+    let mut file = try!(File::open(input).map_err(|error| Error::io(input, error)));
+    let mut content = String::new();
+    try!(file.read_to_string(&mut content).map_err(|error| Error::io(input, error)));
+    match read_games(&content) {
+        Ok(games) => Ok(games),
+        Err(error) => {
+            // The parser does not tell where it failed, so the games are parsed one by one to find the invalid one.
+            let invalid_game = split_games(&content).into_iter().enumerate()
+                .find(|&(_, (_, ref source))| read_games(source).is_err());
+            Err(Error::Syntax {
+                file: Some(input.to_path_buf()),
+                game: invalid_game.as_ref().map(|&(index, _)| index + 1),
+                line: invalid_game.as_ref().map(|&(_, (line, _))| line),
+                message: format!("{:?}", error),
+            })
+        },
+    }
+}
+
+/// Split the PGN `content` into the source of each game, with the number of its first line.
+/// A game starts with a tag outside of a comment, after the moves of the previous game.
+fn split_games(content: &str) -> Vec<(usize, String)> {
+    let mut games: Vec<(usize, String)> = vec![];
+    let mut in_comment = false;
+    let mut in_moves = false;
+    for (index, line) in content.lines().enumerate() {
+        let is_tag = !in_comment && line.trim_left().starts_with('[');
+        if (games.is_empty() && !line.trim().is_empty()) || (is_tag && in_moves) {
+            games.push((index + 1, String::new()));
+            in_moves = false;
+        }
+        if !is_tag && !line.trim().is_empty() {
+            in_moves = true;
+        }
+        for character in line.chars() {
+            match character {
+                '{' => in_comment = true,
+                '}' => in_comment = false,
+                _ => (),
+            }
+        }
+        if let Some(&mut (_, ref mut source)) = games.last_mut() {
+            source.push_str(line);
+            source.push('\n');
+        }
+    }
+    games
+}
+
+/// Get the path of the `number`th file when an output is split in several files: `output-number.extension`.
+fn numbered_path(output: &Path, number: usize) -> PathBuf {
+    let stem = output.file_stem().unwrap_or("".as_ref()).to_string_lossy().into_owned();
+    let mut path = output.with_file_name(format!("{}-{}", stem, number));
+    if let Some(extension) = output.extension() {
+        path.set_extension(extension);
+    }
+    path
+}
+
+pub fn write_file(path: &Path, content: &[u8]) -> Result<()> {
+    let mut file = try!(File::create(path).map_err(|error| Error::io(path, error)));
+    file.write_all(content).map_err(|error| Error::io(path, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chess_pgn_parser::{Game, read_games};
+    use tempdir::TempDir;
+
+    use theme::Theme;
+    use super::{DiagramSize, Document, Mode, Options};
+
+    fn game(tags: &str) -> Game {
+        read_games(&format!("{}\n1. e4 e5 *\n", tags)).unwrap().remove(0)
+    }
+
+    #[test]
+    fn heading_sizes_of_the_groups() {
+        let document = Document::new(vec![], Options::default());
+        assert!(!document.theme_settings(true).contains("heading:"));
+        let grouped = Document::new(vec![], Options { group_by: Some("ECO".to_string()), .. Options::default() });
+        assert_eq!(grouped.theme_settings(true), "heading:\n  h2:\n    font_size: round($base_font_size * 1.5)\n  \
+            h3:\n    font_size: $base_font_size_large\n");
+        assert_eq!(grouped.theme_settings(false), "");
+    }
+
+    #[test]
+    fn running_content_of_the_grouped_games() {
+        let options = Options {
+            group_by: Some("ECO".to_string()),
+            running_footer: Some("{game}|{page}".to_string()),
+            .. Options::default()
+        };
+        let document = Document::new(vec![], options);
+        assert!(document.theme_settings(false).ends_with("footer:\n  height: 0.5in\n  recto:\n    left:\n      \
+            content: '{section-title}'\n    right:\n      content: '{page-number}'\n  verso:\n    left:\n      \
+            content: '{section-title}'\n    right:\n      content: '{page-number}'\n"));
+        let theme = Theme {
+            dir: Some(PathBuf::from("/themes")),
+            name: "chess".to_string(),
+        };
+        assert!(document.document_attributes(&theme).unwrap().contains(":doctype: book\n"));
+    }
+
+    #[test]
+    fn parse_diagram_sizes() {
+        assert_eq!("large".parse(), Ok(DiagramSize::Large));
+        assert_eq!("200".parse(), Ok(DiagramSize::Points(200.0)));
+        assert_eq!("180.5pt".parse(), Ok(DiagramSize::Points(180.5)));
+        assert_eq!("1440".parse(), Ok(DiagramSize::Points(1440.0)));
+        assert!("0".parse::<DiagramSize>().is_err());
+        assert!("inf".parse::<DiagramSize>().is_err());
+        assert!("nan".parse::<DiagramSize>().is_err());
+        assert!("1e9".parse::<DiagramSize>().is_err());
+        assert!("huge".parse::<DiagramSize>().is_err());
+    }
+
+    #[test]
+    fn mode_of_games() {
+        let played = game("[Result \"1-0\"]");
+        assert_eq!(Mode::Repertoire.of(&played), Mode::Repertoire);
+        assert_eq!(Mode::Game.of(&game("[Result \"*\"]")), Mode::Game);
+        assert_eq!(Mode::Auto.of(&game("[SheetMode \"repertoire\"]\n[Result \"1-0\"]")), Mode::Repertoire);
+        assert_eq!(Mode::Auto.of(&game("[SheetMode \"game\"]")), Mode::Game);
+        assert_eq!(Mode::Auto.of(&game("[SheetMode \"auto\"]\n[Result \"1-0\"]")), Mode::Game);
+        assert_eq!(Mode::Auto.of(&game("[SheetMode \"other\"]")), Mode::Repertoire);
+        assert_eq!(Mode::Auto.of(&played), Mode::Game);
+        assert_eq!(Mode::Auto.of(&game("[Result \"*\"]")), Mode::Repertoire);
+        assert_eq!(Mode::Auto.of(&game("[WhiteElo \"2700\"]")), Mode::Game);
+        assert_eq!(Mode::Auto.of(&game("[BlackElo \" 2650 \"]")), Mode::Game);
+        assert_eq!(Mode::Auto.of(&game("[WhiteElo \"?\"]\n[BlackElo \"0\"]")), Mode::Repertoire);
+        assert_eq!(Mode::Auto.of(&game("[White \"Ruy Lopez\"]\n[Black \"Closed\"]")), Mode::Repertoire);
+    }
+
+    #[test]
+    fn to_asciidoc_with_the_builtin_theme() {
+        let games = read_games("[White \"Ruy Lopez\"]\n[Black \"Closed\"]\n\n1. e4 e5 (1... c5) *\n").unwrap();
+        let document = Document::new(games, Options::default());
+        let theme_dir = TempDir::new("pgn2pdf-test").unwrap();
+        let asciidoc = document.to_asciidoc(theme_dir.path()).unwrap();
+        assert!(asciidoc.contains(&format!(":pdf-stylesdir: {}\n:pdf-style: chess\n", theme_dir.path().display())));
+        assert!(theme_dir.path().join("chess-theme.yml").is_file());
+    }
+}
@@ -0,0 +1,169 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::result;
+
+use self::Error::*;
+
+pub type Result<T> = result::Result<T, Error>;
+
+/// Errors that can happen while converting a PGN file.
+#[derive(Debug)]
+pub enum Error {
+    /// Error when the backend program is missing or fails.
+    /// `status` is the exit code of the program, if it exited.
+    Backend {
+        program: String,
+        status: Option<i32>,
+        message: String,
+    },
+    /// Error when a move of a game cannot be played from the current position.
+    /// `game` is the 1-based index of the game in the file.
+    IllegalMove {
+        file: Option<PathBuf>,
+        game: Option<usize>,
+        move_: String,
+    },
+    /// Error when reading or writing a file.
+    Io {
+        file: Option<PathBuf>,
+        error: io::Error,
+    },
+    /// Error when the PGN file is not syntactically valid.
+    /// `game` is the 1-based index of the first invalid game, which starts at the line `line`, and `message` is the
+    /// error of the parser.
+    Syntax {
+        file: Option<PathBuf>,
+        game: Option<usize>,
+        line: Option<usize>,
+        message: String,
+    },
+    /// Error when filling the document template.
+    Template {
+        file: Option<PathBuf>,
+        message: String,
+    },
+    /// Error when the theme cannot be found or used.
+    Theme {
+        name: String,
+        message: String,
+    },
+}
+
+impl Error {
+    /// Add the file and the game index to the context of an error raised while rendering a game.
+    pub fn in_game(self, file: Option<&Path>, game: usize) -> Self {
+        match self {
+            IllegalMove { file: None, game: None, move_ } =>
+                IllegalMove {
+                    file: file.map(Path::to_path_buf),
+                    game: Some(game),
+                    move_: move_,
+                },
+            Syntax { file: None, game: None, line, message } =>
+                Syntax {
+                    file: file.map(Path::to_path_buf),
+                    game: Some(game),
+                    line: line,
+                    message: message,
+                },
+            error => error,
+        }
+    }
+
+    /// Create an I/O error related to `file`.
+    pub fn io<P: AsRef<Path>>(file: P, error: io::Error) -> Self {
+        Io {
+            file: Some(file.as_ref().to_path_buf()),
+            error: error,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> result::Result<(), fmt::Error> {
+        match *self {
+            Backend { ref program, status: Some(code), ref message } =>
+                write!(formatter, "{} failed with exit code {}:\n{}", program, code, message.trim_right()),
+            Backend { ref program, status: None, ref message } =>
+                write!(formatter, "{}: {}", program, message.trim_right()),
+            IllegalMove { ref file, game: Some(game), ref move_ } =>
+                write!(formatter, "{}game {}: illegal move {}", FilePrefix(file), game, move_),
+            IllegalMove { ref file, game: None, ref move_ } =>
+                write!(formatter, "{}illegal move {}", FilePrefix(file), move_),
+            Io { ref file, ref error } => write!(formatter, "{}{}", FilePrefix(file), error),
+            Syntax { ref file, game: Some(game), line: Some(line), ref message } =>
+                write!(formatter, "{}game {} (line {}): invalid PGN syntax ({})", FilePrefix(file), game, line, message),
+            Syntax { ref file, game: Some(game), line: None, ref message } =>
+                write!(formatter, "{}game {}: invalid PGN syntax ({})", FilePrefix(file), game, message),
+            Syntax { ref file, ref message, .. } =>
+                write!(formatter, "{}invalid PGN syntax ({})", FilePrefix(file), message),
+            Template { ref file, ref message } => write!(formatter, "{}template error: {}", FilePrefix(file), message),
+            Theme { ref name, ref message } => write!(formatter, "theme {}: {}", name, message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Backend { .. } => "backend error",
+            IllegalMove { .. } => "illegal move",
+            Io { ref error, .. } => error.description(),
+            Syntax { .. } => "PGN syntax error",
+            Template { .. } => "template error",
+            Theme { .. } => "theme error",
+        }
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            Io { ref error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Io {
+            file: None,
+            error: error,
+        }
+    }
+}
+
+/// Show the file name, if any, before an error message.
+struct FilePrefix<'a>(&'a Option<PathBuf>);
+
+impl<'a> Display for FilePrefix<'a> {
+    fn fmt(&self, formatter: &mut Formatter) -> result::Result<(), fmt::Error> {
+        match *self.0 {
+            Some(ref file) => write!(formatter, "{}: ", file.display()),
+            None => Ok(()),
+        }
+    }
+}
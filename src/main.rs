@@ -19,92 +19,87 @@
  * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
  */
 
+
 /*
- * TODO: supprimer les annotations dans les commentaires.
- * FIXME: missing comments and wrong foot-note number inside variation.
- * FIMXE: change theme to remove even table row color.
- *
- * TODO: improve error handling.
  * TODO: ask before overriding file.
  * TODO: open preview in another process.
  */
 
-extern crate chess_pgn_parser;
 extern crate docopt;
 extern crate open;
+extern crate pgn2pdf;
 extern crate rustc_serialize;
-extern crate tempdir;
-
-mod game;
 
-use std::collections::HashMap;
-use std::cmp::max;
-use std::error::Error;
-use std::ffi::{OsString};
-use std::fmt::{self, Display, Formatter};
-use std::fs::File;
-use std::io::{Read, Write};
-use std::iter::{once, repeat};
+use std::io::{self, Write, stderr};
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::exit;
+use std::str::FromStr;
 
-use chess_pgn_parser::{Game, GameMove, Square, read_games};
-use chess_pgn_parser::AnnotationSymbol::{Blunder, Brilliant, Dubious, Good, Interesting, Mistake};
-use chess_pgn_parser::Move::{BasicMove, CastleKingside, CastleQueenside};
-use chess_pgn_parser::MoveNumber::White;
-use chess_pgn_parser::Piece::{self, Bishop, King, Knight, Pawn, Queen, Rook};
 use docopt::Docopt;
-use tempdir::TempDir;
-
-use game::ChessGame;
-use self::ShowMoveOptions::*;
-
-const MOVES_TO_SHOW: usize = 9;
-
-#[derive(PartialEq)]
-enum ShowMoveOptions {
-    Normal,
-    WithoutNum,
-}
-
-#[derive(Debug)]
-struct ParseError {
-    error: String,
-}
-
-impl ParseError {
-    fn new(error: &str) -> Self {
-        ParseError {
-            error: error.to_string(),
-        }
-    }
-}
-
-impl Display for ParseError {
-    fn fmt(&self, formatter: &mut Formatter) -> std::result::Result<(), fmt::Error> {
-        write!(formatter, "{}", self.error)
-    }
-}
+use pgn2pdf::{Document, Error, Format, Options, Page, Result};
+use pgn2pdf::render::svg::{Colors, SvgOptions};
 
-impl Error for ParseError {
-    fn description(&self) -> &str {
-        &self.error
-    }
-}
+const EXIT_USAGE: i32 = 1;
+const EXIT_PARSE: i32 = 2;
+const EXIT_RENDER: i32 = 3;
+const EXIT_IO: i32 = 4;
 
-type Result<T> = std::result::Result<T, Box<Error>>;
-
-const THEME_DIR: &'static str = "/usr/local/share/pgn2pdf/";
 const USAGE: &'static str = "
 PGN to PDF converter.
 
 Usage:
-  pgn2pdf <filename> [--output=<output>]
-  pgn2pdf <filename> [--preview]
+  pgn2pdf <filename> [options] [--output=<output>]
+  pgn2pdf <filename> [options] [--preview]
 
 Options:
+  -f --format=<format>  Set output format: pdf, adoc, epub, html, latex, markdown, svg or text [default: pdf].
+                        The text is printed on the standard output when no output file is set.
+  --author=<author>     Set the author of the PDF, shown on its cover page and written in its metadata.
+  --colors=<scheme>     Set the colours of the SVG diagrams: brown, green, grayscale or high-contrast [default: brown].
+  --coordinates=<c>     Set where to show the coordinates of the diagrams: border, inside (SVG diagrams only) or none
+                        [default: border].
+  --cover=<image>       Show this image on the first page of the PDF.
+  --diagram=<style>     Draw the PDF diagrams with a chess font or as SVG images: font or svg [default: font].
+  --diagram-font=<f>    Set the chess font of the PDF diagrams, which must be in the theme: merida-unicode, alpha,
+                        cases, leipzig or merida, the built-in theme having only merida-unicode
+                        [default: merida-unicode].
+  --diagram-size=<s>    Set the width of the PDF diagram: small, medium, large or a size in points [default: medium].
+  --emit-adoc=<file>    Also write the AsciiDoc document given to asciidoctor-pdf to this file.
+  --group-by=<tag>      Group the games of the PDF under a heading with the value of this tag, like ECO.
+  --header              Show the players, the event, the date, the result and the other tags of the repertoire lines,
+                        always shown for the played games.
+  --index               Add an index of the openings and of the players of the played games at the end of the PDF.
+  --indicator=<i>       Set how to show the side to move: dot, arrow, text or none [default: dot].
+  --inline-comments     Show the comments of the moves past the variation table between parentheses after them,
+                        instead of in foot-notes.
+  --keywords=<k>        Set the keywords of the PDF metadata, separated by commas.
+  --layout=<layout>     Set the PDF layout of the moves and the foot-notes: one-column or two-columns
+                        [default: one-column].
+  --link-notes          Link the foot-note numbers of the moves of the PDF to their foot-notes, and back.
+  --margin=<margin>     Set the PDF page margins: one, two or four lengths separated by commas, like 0.5in,15mm.
+  --mode=<mode>         Set whether the games are played games or repertoire lines, whose White and Black tags are
+                        the opening and the variation: game, repertoire or auto, which uses the SheetMode tag of
+                        each game or guesses from its result and Elo tags [default: auto].
+  --note-diagram-size=<s>  Set the width of the diagrams asked in comments with [%diagram] [default: small].
   -o --output=<output>  Set output file.
-  -p --preview          Preview the file in the system PDF viewer instead of saving it to a file.
+  --orientation=<o>     Set the PDF page orientation: portrait or landscape.
+  --page-size=<size>    Set the PDF page size: A3, A4, A5, A6, B5, LEGAL, LETTER or a size like 6inx9in.
+                        The variation table has as many moves as fit the page, which is LETTER with the margins of the
+                        built-in theme when only the orientation or the margins are set, whatever the theme.
+  --running-footer=<content>  Set the footer of the PDF pages: one, two or three parts separated by |, centered,
+                        on the sides or on the left, center and right, which can contain {page}, {pages}, {title},
+                        {game} and {date}, like '{game}|Page {page} of {pages}'.
+  --running-header=<content>  Set the header of the PDF pages, like the footer.
+  --subtitle=<s>        Set the subtitle of the PDF cover page, also written as the subject of its metadata.
+  --template=<file>     Set the AsciiDoc template of the PDF pages, whose {{name}} variables are replaced: title,
+                        diagram, moves, movetext, variations, footnotes, fen, tags, white, black, players, event,
+                        site, date, round, result, eco, opening, tag.<name>, game, group, heading, header, index,
+                        page, theme, stylesdir and templatedir.
+  --title=<title>       Set the title of the document, shown on a cover page in the PDF.
+  --toc                 Add a table of contents to the PDF, and the games and their groups to its outline.
+  -t --theme=<theme>    Set the PDF theme: a name looked up in $PGN2PDF_THEME_PATH and the pgn2pdf directory of
+                        the XDG data directories, or the path of a <name>-theme.yml file [default: chess].
+  -p --preview          Preview the file in the system viewer instead of saving it to a file.
   -h --help             Show this screen.
   --version             Show version.
 ";
@@ -112,29 +107,129 @@ Options:
 #[derive(Debug, RustcDecodable)]
 struct Args {
     arg_filename: String,
+    flag_author: Option<String>,
+    flag_colors: String,
+    flag_coordinates: String,
+    flag_cover: Option<String>,
+    flag_diagram: String,
+    flag_diagram_font: String,
+    flag_diagram_size: String,
+    flag_emit_adoc: Option<String>,
+    flag_format: String,
+    flag_group_by: Option<String>,
+    flag_header: bool,
+    flag_index: bool,
+    flag_indicator: String,
+    flag_inline_comments: bool,
+    flag_keywords: Option<String>,
+    flag_layout: String,
+    flag_link_notes: bool,
+    flag_margin: Option<String>,
+    flag_mode: String,
+    flag_note_diagram_size: String,
+    flag_orientation: Option<String>,
     flag_output: Option<String>,
+    flag_page_size: Option<String>,
     flag_preview: bool,
+    flag_running_footer: Option<String>,
+    flag_running_header: Option<String>,
+    flag_subtitle: Option<String>,
+    flag_template: Option<String>,
+    flag_theme: String,
+    flag_title: Option<String>,
+    flag_toc: bool,
 }
 
 fn main() {
     let args: Args = Docopt::new(USAGE)
         .and_then(|decoder| decoder.decode())
         .unwrap_or_else(|error| error.exit());
-    match convert(&args.arg_filename, args.flag_output, args.flag_preview) {
-        Ok(output) =>
-            if args.flag_preview {
-                open_pdf_viewer(&output);
-            },
-        Err(error) => println!("{}", error),
+    let coordinates = parse_arg(&args.flag_coordinates);
+    let indicator = parse_arg(&args.flag_indicator);
+    let options = Options {
+        author: args.flag_author.clone(),
+        coordinates: coordinates,
+        cover: args.flag_cover.as_ref().map(PathBuf::from),
+        diagram: parse_arg(&args.flag_diagram),
+        diagram_font: parse_arg(&args.flag_diagram_font),
+        diagram_size: parse_arg(&args.flag_diagram_size),
+        format: parse_arg(&args.flag_format),
+        group_by: args.flag_group_by.clone(),
+        header: args.flag_header,
+        index: args.flag_index,
+        indicator: indicator,
+        inline_comments: args.flag_inline_comments,
+        keywords: args.flag_keywords.clone(),
+        layout: parse_arg(&args.flag_layout),
+        link_notes: args.flag_link_notes,
+        mode: parse_arg(&args.flag_mode),
+        note_diagram_size: parse_arg(&args.flag_note_diagram_size),
+        page: Page {
+            margin: args.flag_margin.as_ref().map(|margin| parse_arg(margin)),
+            orientation: args.flag_orientation.as_ref().map(|orientation| parse_arg(orientation)),
+            size: args.flag_page_size.as_ref().map(|size| parse_arg(size)),
+        },
+        running_footer: args.flag_running_footer.clone(),
+        running_header: args.flag_running_header.clone(),
+        subtitle: args.flag_subtitle.clone(),
+        svg: SvgOptions {
+            colors: Colors::from_scheme(parse_arg(&args.flag_colors)),
+            coordinates: coordinates,
+            indicator: indicator,
+            .. SvgOptions::default()
+        },
+        template: args.flag_template.as_ref().map(PathBuf::from),
+        theme: args.flag_theme.clone(),
+        title: args.flag_title.clone(),
+        toc: args.flag_toc,
+    };
+    let preview = args.flag_preview;
+    let result = convert(&args.arg_filename, args.flag_output, args.flag_emit_adoc, options, preview)
+        .and_then(|output|
+            match output {
+                Some(ref output) if preview => open_viewer(output),
+                _ => Ok(()),
+            }
+        );
+    if let Err(error) = result {
+        let _ = writeln!(stderr(), "pgn2pdf: {}", error);
+        exit(exit_code(&error));
     }
 }
 
-fn convert(filename: &str, output: Option<String>, preview: bool) -> Result<String> {
-    let tempdir = try!(TempDir::new("pgn2pdf"));
-    let games = try!(read_pgn_games(filename));
+fn parse_arg<T: FromStr<Err=String>>(arg: &str) -> T {
+    arg.parse().unwrap_or_else(|error| {
+        let _ = writeln!(stderr(), "pgn2pdf: {}", error);
+        exit(EXIT_USAGE);
+    })
+}
+
+fn exit_code(error: &Error) -> i32 {
+    match *error {
+        Error::IllegalMove { .. } | Error::Syntax { .. } => EXIT_PARSE,
+        Error::Backend { .. } | Error::Template { .. } | Error::Theme { .. } => EXIT_RENDER,
+        Error::Io { .. } => EXIT_IO,
+    }
+}
+
+/// Convert the file and return the output file, if any.
+fn convert(filename: &str, output: Option<String>, emit_adoc: Option<String>, options: Options, preview: bool)
+    -> Result<Option<String>>
+{
+    let format = options.format;
+    let extension = format.extension();
+    let document = try!(Document::open(filename, options));
+    if let Some(emit_adoc) = emit_adoc {
+        try!(document.write_asciidoc(emit_adoc));
+    }
+    if format == Format::Text && output.is_none() {
+        let text = try!(document.to_text());
+        print!("{}", text);
+        return Ok(None);
+    }
     let output = output.unwrap_or_else(|| {
         let mut output = PathBuf::from(&filename);
-        output.set_extension("pdf");
+        output.set_extension(extension);
         let output = output.file_name();
         let output = output.unwrap().to_str().unwrap().to_string();
         if preview {
@@ -145,251 +240,19 @@ fn convert(filename: &str, output: Option<String>, preview: bool) -> Result<Stri
         }
     });
     // TODO: ask for which game to print.
-    for game in games {
-        let input = try!(write_asciidoc(&tempdir, game, filename));
-        try!(run_asciidoc(input, &output));
-    }
-    Ok(output)
-}
-
-fn open_pdf_viewer(filename: &str) {
-    if let Err(error) = open::that(filename) {
-        println!("{}", error);
+    let warnings = try!(document.write(&output));
+    for warning in warnings {
+        let _ = writeln!(stderr(), "{}", warning);
     }
+    Ok(Some(output))
 }
 
-fn run_asciidoc(filename: OsString, output: &str) -> Result<()> {
-    let _ = try!(Command::new("asciidoctor-pdf")
-        .arg(&filename)
-        .arg("-o")
-        .arg(output)
-        .status());
-    Ok(())
-}
-
-fn read_pgn_games(input: &str) -> Result<Vec<Game>> {
-    let mut file = try!(File::open(input));
-    let mut content = String::new();
-    try!(file.read_to_string(&mut content));
-    if let Ok(games) = read_games(&content) {
-        Ok(games)
+fn open_viewer(filename: &str) -> Result<()> {
+    let status = try!(open::that(filename).map_err(|error| Error::io(filename, error)));
+    if status.success() {
+        Ok(())
     }
     else {
-        Err(Box::new(ParseError::new("parse error")))
-    }
-}
-
-fn get_diagram(moves: &[&GameMove]) -> String {
-    let mut game = ChessGame::initial();
-    for game_move in moves {
-        game.play(game_move);
-    }
-    game.show()
-}
-
-fn get_initial_moves(game: &Game) -> Vec<&GameMove> {
-    game.moves.iter()
-        .take_while(|game_move| game_move.variations.is_empty())
-        .collect()
-}
-
-fn get_title(game: &Game) -> String {
-    let tags: HashMap<String, String> = game.tags.iter().cloned().collect();
-    match (tags.get("White"), tags.get("Black")) {
-        (Some(opening), Some(variation)) => format!("{} - {}", opening, variation),
-        (None, Some(name)) | (Some(name), None) => name.clone(),
-        (None, None) => String::new(),
+        Err(Error::io(filename, io::Error::new(io::ErrorKind::Other, "cannot open the file in the viewer")))
     }
 }
-
-fn is_white_move(game_move: &GameMove) -> bool {
-    if let Some(ref number) = game_move.number {
-        if let White(_) = *number {
-            return true;
-        }
-    }
-    false
-}
-
-fn extract_variations(moves: &[GameMove], comments: &mut Vec<String>) -> String {
-    let first_move_is_black = !is_white_move(&moves[0]);
-    let mut first_white_moves = vec![];
-    let mut first_black_moves = vec![];
-    let mut iterator = moves.iter();
-    let mut move_num = 0;
-    while let Some(game_move) = iterator.next() {
-        if is_white_move(game_move) {
-            first_white_moves.push(move_to_string(game_move, WithoutNum, comments));
-        }
-        else {
-            first_black_moves.push(move_to_string(game_move, WithoutNum, comments));
-            move_num += 1;
-        }
-        if move_num >= MOVES_TO_SHOW {
-            break;
-        }
-    }
-    let variation: Vec<_> = iterator.map(|game_move|
-        move_to_string(game_move, Normal, &mut vec![])).collect();
-    let variation = variation.join(" ");
-    if !variation.is_empty() {
-        let last_move = first_black_moves.last_mut().unwrap();
-        comments.push(variation);
-        last_move.push_str(&format!("^{}^", comments.len()));
-    }
-    // TODO: add ¹ for variations.
-    // TODO: add variation evaluation.
-    if first_move_is_black {
-        first_white_moves.insert(0, "…".to_string());
-    }
-    let remaining_white = max(0, MOVES_TO_SHOW as i32 - first_white_moves.len() as i32) as usize;
-    let rest_of_white_row: Vec<_> = repeat("|").take(remaining_white).collect();
-    let remaining_black = max(0, MOVES_TO_SHOW as i32 - first_black_moves.len() as i32) as usize;
-    let rest_of_black_row: Vec<_> = repeat("|").take(remaining_black).collect();
-    format!("| {}\n{}\n| | {}\n{}\n", first_white_moves.join("\n| "), rest_of_white_row.join("\n"),
-        first_black_moves.join("\n| "), rest_of_black_row.join("\n"))
-}
-
-fn get_variations(game: &Game, start_move_num: usize, comments: &mut Vec<String>) -> String {
-    let mut result = String::new();
-    let mut moves = game.moves.iter()
-        .skip(start_move_num);
-    if let Some(start_move) = moves.next() {
-        if !start_move.variations.is_empty() {
-            result += &format!("[cols=\"1, {}*3\"]\n|===\n| ", MOVES_TO_SHOW);
-            for num in start_move_num .. start_move_num + MOVES_TO_SHOW {
-                result += &format!("|{} ", num - 1);
-            }
-            result += "\n\n";
-            let mut variations = vec![];
-            variations.push(format!("| *1*\n{}", extract_variations(&game.moves[start_move_num..], comments)));
-            let separator = once("|{nbsp}")
-                .chain(repeat("|").take(MOVES_TO_SHOW))
-                .chain(once("\n"))
-                .collect::<String>();
-            for (index, variation) in start_move.variations.iter().enumerate() {
-                variations.push(format!("| *{}*\n{}", index + 2, extract_variations(&variation.moves, comments)));
-            }
-            result += &variations.join(&separator);
-            result += "|===";
-        }
-    }
-    result
-}
-
-fn format_comments(comments: Vec<String>) -> String {
-    if comments.is_empty() {
-        String::new()
-    }
-    else {
-        let comments: Vec<_> =
-            comments.iter().enumerate()
-                .map(|(index, comment)|
-                     format!("
-[cols=\"1,24\"]
-|===
-|^{}^
-|{}
-|===
-                     ", index + 1, comment)
-                )
-                .collect();
-        comments.join("\n")
-    }
-}
-
-fn write_asciidoc(tempdir: &TempDir, game: Game, filename: &str) -> Result<OsString> {
-    let title = get_title(&game);
-    let mut output = PathBuf::from(filename);
-    output.set_extension("adoc");
-    let filename = output.file_name();
-    let output_file = filename.as_ref().unwrap();
-    let file_path = tempdir.path().join(output_file);
-    let mut file = try!(File::create(&file_path));
-    let initial_moves = get_initial_moves(&game);
-    let diagram = get_diagram(&initial_moves);
-    let mut comments = vec![];
-    let moves: Vec<String> = initial_moves.iter()
-        .map(|game_move| move_to_string(game_move, Normal, &mut comments))
-        .collect();
-    let moves = moves.join(" ");
-    let variations = get_variations(&game, initial_moves.len(), &mut comments);
-    let comments = format_comments(comments);
-    try!(write!(file, include_str!("../themes/template.adoc"), THEME_DIR, title, diagram, moves, variations, comments));
-    Ok(file_path.into_os_string())
-}
-
-fn move_to_string(game_move: &GameMove, options: ShowMoveOptions, comments: &mut Vec<String>) -> String {
-    let mut string = String::new();
-    if options != WithoutNum {
-        if let Some(White(number)) = game_move.number {
-            string += &format!("{}.", number);
-        }
-    }
-    let mov =
-        match game_move.move_.move_ {
-            BasicMove { ref from, is_capture, ref piece, ref promoted_to, ref to } => {
-                let piece = piece_to_string(piece);
-                let from = square_to_string(from);
-                let symbol =
-                    if is_capture {
-                        "x"
-                    }
-                    else {
-                        ""
-                    };
-                let to = square_to_string(to);
-                let promotion =
-                    if let Some(ref piece) = *promoted_to {
-                        format!("={}", piece_to_string(piece))
-                    }
-                    else {
-                        String::new()
-                    };
-                format!("{}{}{}{}{}", piece, from, symbol, to, promotion)
-            },
-            CastleKingside => "O-O".to_string(),
-            CastleQueenside => "O-O-O".to_string(),
-        };
-    string += &mov;
-    // TODO: support nags.
-    if let Some(ref annotation) = game_move.move_.annotation_symbol {
-        let annotation =
-            match *annotation {
-                Blunder => "??",
-                Brilliant => "!!",
-                Dubious => "?!",
-                Good => "!",
-                Interesting => "!?",
-                Mistake => "?",
-            };
-        string += annotation;
-    }
-    if game_move.move_.is_check {
-        string += "+";
-    }
-    else if game_move.move_.is_checkmate {
-        string += "#";
-    }
-    if let Some(ref comment) = game_move.comment {
-        comments.push(comment.replace('\n', " "));
-        string += &format!("^{}^", comments.len());
-    }
-    string
-}
-
-fn piece_to_string(piece: &Piece) -> &str {
-    // TODO: translate in english.
-    match *piece {
-        Bishop => "F",
-        King => "R",
-        Knight => "C",
-        Pawn => "",
-        Queen => "D",
-        Rook => "T",
-    }
-}
-
-fn square_to_string(square: &Square) -> String {
-    format!("{:?}", square).to_lowercase().replace('x', "")
-}
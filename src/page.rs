@@ -0,0 +1,259 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! Page geometry of the PDF, overriding the one of the theme.
+
+use std::str::FromStr;
+
+use render::MOVES_TO_SHOW;
+
+/// Size of the pages, when not set in the options.
+const DEFAULT_SIZE: PageSize = PageSize { name: None, width: 612.0, height: 792.0 };
+/// Margins of the pages, when not set in the options.
+const DEFAULT_MARGIN: Margin = Margin([36.0, 48.24, 48.24, 48.24]);
+/// Width of a move column of the variation table, in points.
+const COLUMN_WIDTH: f32 = 54.0;
+/// Fewest move columns shown in the variation table.
+const MIN_COLUMNS: usize = 4;
+/// Most move columns shown in the variation table.
+const MAX_COLUMNS: usize = 16;
+/// Longest length accepted, in points: the largest page size of a PDF.
+const MAX_LENGTH: f32 = 14400.0;
+
+/// Named page sizes, in points.
+const SIZES: &'static [(&'static str, f32, f32)] = &[
+    ("A3", 841.89, 1190.55),
+    ("A4", 595.28, 841.89),
+    ("A5", 419.53, 595.28),
+    ("A6", 297.64, 419.53),
+    ("B5", 498.9, 708.66),
+    ("LEGAL", 612.0, 1008.0),
+    ("LETTER", 612.0, 792.0),
+];
+
+/// Orientation of the pages.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Orientation {
+    Landscape,
+    Portrait,
+}
+
+impl FromStr for Orientation {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, String> {
+        match string {
+            "landscape" => Ok(Orientation::Landscape),
+            "portrait" => Ok(Orientation::Portrait),
+            _ => Err(format!("unknown orientation {}", string)),
+        }
+    }
+}
+
+/// Size of the pages, in portrait orientation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageSize {
+    name: Option<&'static str>,
+    /// Width, in points.
+    pub width: f32,
+    /// Height, in points.
+    pub height: f32,
+}
+
+impl FromStr for PageSize {
+    type Err = String;
+
+    /// Parse a named size, like `A4`, or a width and a height, like `6inx9in`.
+    fn from_str(string: &str) -> Result<Self, String> {
+        let upper = string.to_uppercase();
+        if let Some(&(name, width, height)) = SIZES.iter().find(|&&(name, _, _)| name == upper) {
+            return Ok(PageSize {
+                name: Some(name),
+                width: width,
+                height: height,
+            });
+        }
+        let mut lengths = string.splitn(2, 'x');
+        match (lengths.next().and_then(parse_length), lengths.next().and_then(parse_length)) {
+            (Some(width), Some(height)) if width > 0.0 && height > 0.0 => Ok(PageSize {
+                name: None,
+                width: width,
+                height: height,
+            }),
+            _ => Err(format!("invalid page size {}", string)),
+        }
+    }
+}
+
+impl PageSize {
+    fn to_attribute(&self) -> String {
+        match self.name {
+            Some(name) => name.to_string(),
+            None => format!("[{}, {}]", round(self.width), round(self.height)),
+        }
+    }
+}
+
+/// Margins of the pages, in points: top, right, bottom and left.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Margin(pub [f32; 4]);
+
+impl FromStr for Margin {
+    type Err = String;
+
+    /// Parse one, two or four lengths separated by commas, in the CSS order.
+    fn from_str(string: &str) -> Result<Self, String> {
+        let lengths: Option<Vec<_>> = string.trim_matches(|c| c == '[' || c == ']').split(',')
+            .map(parse_length)
+            .collect();
+        let lengths = lengths.unwrap_or_default();
+        match lengths.len() {
+            1 => Ok(Margin([lengths[0]; 4])),
+            2 => Ok(Margin([lengths[0], lengths[1], lengths[0], lengths[1]])),
+            4 => Ok(Margin([lengths[0], lengths[1], lengths[2], lengths[3]])),
+            _ => Err(format!("invalid margin {}", string)),
+        }
+    }
+}
+
+/// Page options overriding the theme.
+#[derive(Clone, Debug, Default)]
+pub struct Page {
+    pub margin: Option<Margin>,
+    pub orientation: Option<Orientation>,
+    pub size: Option<PageSize>,
+}
+
+impl Page {
+    /// Get the AsciiDoc attributes setting the page options, one per line.
+    pub fn attributes(&self) -> String {
+        let mut attributes = String::new();
+        if let Some(ref size) = self.size {
+            attributes += &format!(":pdf-page-size: {}\n", size.to_attribute());
+        }
+        if let Some(orientation) = self.orientation {
+            let layout =
+                match orientation {
+                    Orientation::Landscape => "landscape",
+                    Orientation::Portrait => "portrait",
+                };
+            attributes += &format!(":pdf-page-layout: {}\n", layout);
+        }
+        if let Some(Margin(margin)) = self.margin {
+            let margin: Vec<_> = margin.iter().map(|&length| round(length).to_string()).collect();
+            attributes += &format!(":pdf-page-margin: [{}]\n", margin.join(", "));
+        }
+        attributes
+    }
+
+    /// Get the number of move columns of the variation table fitting in the width of the page.
+    /// The size and the margins of the theme are unknown, so the ones of the built-in theme are used when not set.
+    pub fn columns(&self) -> usize {
+        if self.size.is_none() && self.orientation.is_none() && self.margin.is_none() {
+            return MOVES_TO_SHOW;
+        }
+        let size = self.size.as_ref().unwrap_or(&DEFAULT_SIZE);
+        let width =
+            match self.orientation {
+                Some(Orientation::Landscape) => size.width.max(size.height),
+                _ => size.width.min(size.height),
+            };
+        let Margin(margin) = self.margin.clone().unwrap_or(DEFAULT_MARGIN);
+        let width = width - margin[1] - margin[3];
+        // The first column, holding the line numbers, takes the width of a third of a move column.
+        let columns = (width / COLUMN_WIDTH - 1.0 / 3.0).floor();
+        if columns < MIN_COLUMNS as f32 {
+            MIN_COLUMNS
+        }
+        else if columns > MAX_COLUMNS as f32 {
+            MAX_COLUMNS
+        }
+        else {
+            columns as usize
+        }
+    }
+}
+
+/// Round a length in points to hundredths.
+fn round(length: f32) -> f32 {
+    (length * 100.0).round() / 100.0
+}
+
+/// Parse a length, like `0.5in`, to points. The length must be finite, not negative and at most the largest page
+/// size of a PDF.
+fn parse_length(string: &str) -> Option<f32> {
+    let string = string.trim();
+    let units = [("in", 72.0), ("mm", 72.0 / 25.4), ("cm", 72.0 / 2.54), ("pt", 1.0)];
+    let (number, factor) = units.iter()
+        .find(|&&(unit, _)| string.ends_with(unit))
+        .map(|&(unit, factor)| (&string[..string.len() - unit.len()], factor))
+        .unwrap_or((string, 1.0));
+    number.trim().parse::<f32>().ok()
+        .and_then(|number|
+            if number.is_finite() && number >= 0.0 && number * factor <= MAX_LENGTH {
+                Some(number * factor)
+            }
+            else {
+                None
+            }
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use render::MOVES_TO_SHOW;
+
+    use super::{Margin, Orientation, Page, PageSize, parse_length};
+
+    #[test]
+    fn parse_lengths() {
+        assert_eq!(parse_length("72"), Some(72.0));
+        assert_eq!(parse_length(" 0.5in "), Some(36.0));
+        assert_eq!(parse_length("10pt"), Some(10.0));
+        assert_eq!(parse_length("0"), Some(0.0));
+        assert_eq!(parse_length("25.4mm"), Some(72.0));
+        assert_eq!(parse_length("-1in"), None);
+        assert_eq!(parse_length("inf"), None);
+        assert_eq!(parse_length("nan"), None);
+        assert_eq!(parse_length("1e30"), None);
+        assert_eq!(parse_length("1ft"), None);
+    }
+
+    #[test]
+    fn parse_page_sizes() {
+        assert_eq!("6inx9in".parse(), Ok(PageSize { name: None, width: 432.0, height: 648.0 }));
+        assert!("infxinf".parse::<PageSize>().is_err());
+        assert!("0x9in".parse::<PageSize>().is_err());
+        assert_eq!("0".parse(), Ok(Margin([0.0; 4])));
+    }
+
+    #[test]
+    fn columns_fitting_the_page() {
+        assert_eq!(Page::default().columns(), MOVES_TO_SHOW);
+        let a5 = Page { size: "A5".parse().ok(), .. Page::default() };
+        assert_eq!(a5.columns(), 5);
+        let landscape = Page { orientation: Some(Orientation::Landscape), size: "A4".parse().ok(), .. Page::default() };
+        assert_eq!(landscape.columns(), 13);
+        let a6 = Page { margin: "1in".parse().ok(), size: "A6".parse().ok(), .. Page::default() };
+        assert_eq!(a6.columns(), 4);
+    }
+}
@@ -0,0 +1,378 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! AsciiDoc backend, whose output is typeset by asciidoctor-pdf.
+
+use std::iter::{once, repeat};
+
+use board::{ChessGame, DiagramFont};
+use document::{DiagramSize, DiagramStyle, Layout, Options};
+use error::Result;
+use template::Template;
+use theme::Theme;
+use super::{Line, Move, Note, Sheet, Variations};
+
+/// Number of moves in each row of a column of the two-column move text, an even number so that each row starts with a
+/// move of white.
+const MOVES_PER_ROW: usize = 16;
+
+/// Render the sheet of the `game`th game with the `template`, typeset with the installed `theme`.
+/// The game starts the `group` of games, if any.
+pub fn render(sheet: &Sheet, game: usize, group: Option<&str>, template: &Template, theme: &Theme, options: &Options)
+    -> Result<String>
+{
+    let diagram =
+        match options.diagram {
+            DiagramStyle::Font => {
+                let mut roles = vec![];
+                if options.diagram_font != DiagramFont::MeridaUnicode {
+                    roles.push(options.diagram_font.role().to_string());
+                }
+                if options.diagram_size != DiagramSize::Medium {
+                    roles.push(options.diagram_size.role());
+                }
+                sheet.position.show_with(options.diagram_font, &roles, options.coordinates, options.indicator)
+            },
+            DiagramStyle::Svg if options.diagram_size == DiagramSize::Medium =>
+                format!("image::{}[align=center]", diagram_file_name(game)),
+            DiagramStyle::Svg => format!("image::{}[align=center, pdfwidth={}]", diagram_file_name(game),
+                pdf_width(options.diagram_size)),
+        };
+    let moves: Vec<_> = sheet.moves.iter()
+        .map(|game_move| format_move(game_move, game, options))
+        .collect();
+    let variations =
+        match sheet.variations {
+            Some(ref variations) => format_variations(variations, game, options),
+            None => String::new(),
+        };
+    // The games are sections below the ones of their groups.
+    let heading =
+        if options.group_by.is_some() {
+            "==="
+        }
+        else {
+            "=="
+        };
+    let tag = |name: &str| {
+        sheet.tags.iter()
+            .find(|&&(ref tag, _)| tag == name)
+            .map(|&(_, ref value)| value.clone())
+            .unwrap_or_default()
+    };
+    template.render(|name| {
+        let value =
+            match name {
+                "black" => tag("Black"),
+                "date" => tag("Date"),
+                "diagram" => diagram.clone(),
+                "eco" => tag("ECO"),
+                "event" => tag("Event"),
+                "fen" => sheet.position.fen(),
+                "footnotes" => format_notes(&sheet.notes, game, options),
+                "game" => game.to_string(),
+                "group" => group.map(|group| format!("== {}\n\n", group)).unwrap_or_default(),
+                "header" => format_header(sheet),
+                "heading" => heading.to_string(),
+                "index" if options.index => format_index_terms(sheet),
+                "index" => String::new(),
+                "moves" => moves.join(" "),
+                "movetext" => format_movetext(&moves, heading, options.layout),
+                "opening" => tag("Opening"),
+                "page" => options.page.attributes(),
+                "players" => format_players(&tag("White"), &tag("Black")),
+                "result" => tag("Result"),
+                "round" => tag("Round"),
+                "site" => tag("Site"),
+                "stylesdir" => theme.dir.as_ref().map(|dir| dir.display().to_string()).unwrap_or_default(),
+                "tags" => format_tags(&sheet.tags),
+                "templatedir" => template.dir().map(|dir| dir.display().to_string()).unwrap_or_default(),
+                "theme" => theme.name.clone(),
+                "title" => sheet.title.clone(),
+                "variations" => variations.clone(),
+                "white" => tag("White"),
+                _ if name.starts_with("tag.") => tag(&name["tag.".len()..]),
+                _ => return None,
+            };
+        Some(value)
+    })
+}
+
+/// Get the name of the SVG image of the diagram of the `game`th game.
+pub fn diagram_file_name(game: usize) -> String {
+    format!("diagram-{}.svg", game)
+}
+
+/// Get the name of the SVG image of the diagram of the `note`th foot-note of the `game`th game.
+pub fn note_diagram_file_name(game: usize, note: usize) -> String {
+    format!("diagram-{}-note-{}.svg", game, note)
+}
+
+fn pdf_width(size: DiagramSize) -> String {
+    format!("{:.2}in", size.points() / 72.0)
+}
+
+/// Format the diagram of the `note`th foot-note of the `game`th game.
+fn format_note_diagram(position: &ChessGame, game: usize, note: usize, options: &Options) -> String {
+    match options.diagram {
+        DiagramStyle::Font => {
+            let roles = [options.diagram_font.role().to_string(), options.note_diagram_size.role()];
+            position.show_with(options.diagram_font, &roles, options.coordinates, options.indicator)
+        },
+        DiagramStyle::Svg => format!("image:{}[pdfwidth={}]", note_diagram_file_name(game, note),
+            pdf_width(options.note_diagram_size)),
+    }
+}
+
+/// Format the moves in bold, in one or two columns, as headings below the `heading` of the game, kept out of the table
+/// of contents.
+/// The two columns are a table with a row per `MOVES_PER_ROW` moves of each column, since asciidoctor-pdf cannot split
+/// a row across pages.
+fn format_movetext(moves: &[String], heading: &str, layout: Layout) -> String {
+    if layout == Layout::OneColumn || moves.len() < 2 {
+        return format!("[discrete]\n{}= *{}*", heading, moves.join(" "));
+    }
+    // Start the second column with a move of white, which shows its number.
+    let mut half = (moves.len() + 1) / 2;
+    if half % 2 == 1 && half + 1 < moves.len() {
+        half += 1;
+    }
+    let mut result = "[cols=\"2*\", frame=none, grid=none]\n|===\n".to_string();
+    let mut second_column = moves[half..].chunks(MOVES_PER_ROW);
+    for moves in moves[..half].chunks(MOVES_PER_ROW) {
+        result += &format_movetext_cell(moves, heading);
+        match second_column.next() {
+            Some(moves) => result += &format_movetext_cell(moves, heading),
+            None => result += "|\n",
+        }
+    }
+    result += "|===";
+    result
+}
+
+/// Format the moves of a cell of the two-column move text.
+fn format_movetext_cell(moves: &[String], heading: &str) -> String {
+    format!("a|\n[discrete]\n{}= *{}*\n", heading, moves.join(" "))
+}
+
+/// Format the header as a paragraph, followed by a new line.
+fn format_header(sheet: &Sheet) -> String {
+    match sheet.header {
+        Some(ref header) => format!("{}\n", header.lines().join(" +\n")),
+        None => String::new(),
+    }
+}
+
+/// Format the hidden terms adding the game to the index of the openings and, for a played game, of the players,
+/// followed by a new line. They must be below the title, since the terms in a title would be shown in the outline.
+fn format_index_terms(sheet: &Sheet) -> String {
+    let tag = |name: &str| {
+        sheet.tags.iter()
+            .find(|&&(ref tag, _)| tag == name)
+            .map(|&(_, ref value)| value.trim())
+            .and_then(|value|
+                if value.is_empty() || value == "?" {
+                    None
+                }
+                else {
+                    Some(value)
+                }
+            )
+    };
+    let mut terms = String::new();
+    let name =
+        match (tag("Opening"), tag("Variation")) {
+            (Some(opening), Some(variation)) => Some(format!("{}, {}", opening, variation)),
+            (Some(opening), None) => Some(opening.to_string()),
+            (None, _) if !sheet.played && !sheet.title.is_empty() => Some(sheet.title.clone()),
+            (None, _) => None,
+        };
+    let opening: Vec<_> = tag("ECO").map(str::to_string).into_iter().chain(name).collect();
+    if !opening.is_empty() {
+        terms += &format!("(((Openings, {})))", index_term(&opening.join(" ")));
+    }
+    if sheet.played {
+        let game = format_players(tag("White").unwrap_or(""), tag("Black").unwrap_or(""));
+        for player in tag("White").into_iter().chain(tag("Black")) {
+            terms += &format!("(((Players, {}, {})))", index_term(player), index_term(&game));
+        }
+    }
+    if !terms.is_empty() {
+        terms.push('\n');
+    }
+    terms
+}
+
+/// Quote an index term containing a comma, which would otherwise start a sub-term.
+fn index_term(term: &str) -> String {
+    if term.contains(',') {
+        format!("\"{}\"", term.replace('"', "'"))
+    }
+    else {
+        term.to_string()
+    }
+}
+
+fn format_players(white: &str, black: &str) -> String {
+    if white.is_empty() || black.is_empty() {
+        format!("{}{}", white, black)
+    }
+    else {
+        format!("{} - {}", white, black)
+    }
+}
+
+/// Format the tags as an AsciiDoc description list.
+fn format_tags(tags: &[(String, String)]) -> String {
+    let items: Vec<_> = tags.iter()
+        .map(|&(ref name, ref value)| format!("{}:: {}", name, value))
+        .collect();
+    items.join("\n")
+}
+
+/// Format a move of the `game`th game followed by the numbers of its foot-notes, linked to them with the
+/// `link_notes` option.
+fn format_move(game_move: &Move, game: usize, options: &Options) -> String {
+    let mut string = game_move.text.clone();
+    for &note in &game_move.notes {
+        if options.link_notes {
+            string += &format!("[[{}]]^<<{},{}>>^", reference_id(game, note), note_id(game, note), note);
+        }
+        else {
+            string += &format!("^{}^", note);
+        }
+    }
+    string
+}
+
+/// Format the number of the `note`th foot-note of the `game`th game, linked back to its move with the `link_notes`
+/// option.
+fn format_note_number(game: usize, note: usize, options: &Options) -> String {
+    if options.link_notes {
+        format!("[[{}]]^<<{},{}>>^", note_id(game, note), reference_id(game, note), note)
+    }
+    else {
+        format!("^{}^", note)
+    }
+}
+
+/// Get the anchor of the `note`th foot-note of the `game`th game.
+fn note_id(game: usize, note: usize) -> String {
+    format!("game{}-note{}", game, note)
+}
+
+/// Get the anchor of the move referencing the `note`th foot-note of the `game`th game.
+fn reference_id(game: usize, note: usize) -> String {
+    format!("game{}-ref{}", game, note)
+}
+
+fn format_line(line: &Line, columns: usize, game: usize, options: &Options) -> String {
+    let white_moves: Vec<_> = line.white.iter().map(|game_move| format_move(game_move, game, options)).collect();
+    let black_moves: Vec<_> = line.black.iter().map(|game_move| format_move(game_move, game, options)).collect();
+    let remaining_white = columns.saturating_sub(white_moves.len());
+    let rest_of_white_row: Vec<_> = repeat("|").take(remaining_white).collect();
+    let remaining_black = columns.saturating_sub(black_moves.len());
+    let rest_of_black_row: Vec<_> = repeat("|").take(remaining_black).collect();
+    format!("| {}\n{}\n| | {}\n{}\n", white_moves.join("\n| "), rest_of_white_row.join("\n"),
+        black_moves.join("\n| "), rest_of_black_row.join("\n"))
+}
+
+fn format_variations(variations: &Variations, game: usize, options: &Options) -> String {
+    // The widths are relative, so the columns are narrower or wider to fill the width of the page.
+    let mut result = format!("[cols=\"1, {}*3\"]\n|===\n| ", variations.columns);
+    for num in variations.first_number .. variations.first_number + variations.columns {
+        result += &format!("|{} ", num);
+    }
+    result += "\n\n";
+    let lines: Vec<_> = variations.lines.iter().enumerate()
+        .map(|(index, line)| format!("| *{}*\n{}", index + 1, format_line(line, variations.columns, game, options)))
+        .collect();
+    let separator = once("|{nbsp}")
+        .chain(repeat("|").take(variations.columns))
+        .chain(once("\n"))
+        .collect::<String>();
+    result += &lines.join(&separator);
+    result += "|===";
+    result
+}
+
+/// Format the `number`th foot-note of the `game`th game.
+fn format_note(note: &Note, game: usize, number: usize, options: &Options) -> String {
+    match *note {
+        Note::Comment(ref comment) => comment.clone(),
+        Note::Diagram(ref comment, ref position) => {
+            let diagram = format_note_diagram(position, game, number, options);
+            if comment.trim().is_empty() {
+                diagram
+            }
+            else {
+                format!("{} +\n{}", comment, diagram)
+            }
+        },
+        Note::Moves(ref moves) => {
+            let moves: Vec<_> = moves.iter().map(|game_move| format_move(game_move, game, options)).collect();
+            moves.join(" ")
+        },
+    }
+}
+
+fn format_notes(notes: &[Note], game: usize, options: &Options) -> String {
+    if notes.is_empty() {
+        String::new()
+    }
+    else if options.layout == Layout::TwoColumns {
+        format_notes_in_columns(notes, game, options)
+    }
+    else {
+        let notes: Vec<_> =
+            notes.iter().enumerate()
+                .map(|(index, note)|
+                     format!("
+[cols=\"1,24\"]
+|===
+|{}
+|{}
+|===
+                     ", format_note_number(game, index + 1, options), format_note(note, game, index + 1, options))
+                )
+                .collect();
+        notes.join("\n")
+    }
+}
+
+/// Format the notes in a table of two columns, filled one after the other.
+fn format_notes_in_columns(notes: &[Note], game: usize, options: &Options) -> String {
+    let half = (notes.len() + 1) / 2;
+    let mut result = "[cols=\"1,11,1,11\"]\n|===\n".to_string();
+    for row in 0 .. half {
+        for index in &[row, row + half] {
+            match notes.get(*index) {
+                Some(note) =>
+                    result += &format!("|{}\n|{}\n", format_note_number(game, index + 1, options),
+                        format_note(note, game, index + 1, options)),
+                None => result += "|\n|\n",
+            }
+        }
+    }
+    result += "|===";
+    result
+}
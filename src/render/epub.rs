@@ -0,0 +1,186 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! EPUB 3 backend, with a reflowable chapter per game.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Cursor, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use zip::{CompressionMethod, ZipWriter};
+use zip::result::ZipError;
+use zip::write::FileOptions;
+
+use error::{Error, Result};
+use super::{Sheet, html};
+use super::html::escape;
+use super::svg::SvgOptions;
+
+const CONTAINER: &'static str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<container version=\"1.0\" xmlns=\"urn:oasis:names:tc:opendocument:xmlns:container\">
+<rootfiles>
+<rootfile full-path=\"OEBPS/content.opf\" media-type=\"application/oebps-package+xml\"/>
+</rootfiles>
+</container>
+";
+
+/// Style added to the one of the HTML backend so that the diagrams scale with the screen.
+const EPUB_STYLE: &'static str = "
+body {
+    margin: 0;
+    max-width: none;
+}
+.diagram svg {
+    height: auto;
+    max-width: 100%;
+    width: 20em;
+}
+.variations {
+    font-size: small;
+}
+";
+
+/// Render the sheets of the games to an EPUB book titled `title`.
+pub fn render(sheets: &[Sheet], title: &str, svg_options: &SvgOptions) -> Result<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(vec![]));
+    let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+    // The mimetype must be the first file of the archive.
+    try!(add_file(&mut writer, "mimetype", b"application/epub+zip", stored));
+    try!(add_file(&mut writer, "META-INF/container.xml", CONTAINER.as_bytes(), stored));
+    try!(add_file(&mut writer, "OEBPS/content.opf", package(sheets, title).as_bytes(), stored));
+    try!(add_file(&mut writer, "OEBPS/nav.xhtml", navigation(sheets, title).as_bytes(), stored));
+    let style = format!("{}{}", html::STYLE, EPUB_STYLE);
+    try!(add_file(&mut writer, "OEBPS/style.css", style.as_bytes(), stored));
+    for (index, sheet) in sheets.iter().enumerate() {
+        let chapter = page(&sheet.title, &html::render_game(sheet, index + 1, svg_options));
+        try!(add_file(&mut writer, &chapter_file_name(index + 1), chapter.as_bytes(), stored));
+    }
+    let cursor = try!(writer.finish().map_err(zip_error));
+    Ok(cursor.into_inner())
+}
+
+fn add_file(writer: &mut ZipWriter<Cursor<Vec<u8>>>, name: &str, content: &[u8], options: FileOptions) -> Result<()> {
+    try!(writer.start_file(name, options).map_err(zip_error));
+    try!(writer.write_all(content));
+    Ok(())
+}
+
+fn chapter_file_name(game: usize) -> String {
+    format!("OEBPS/{}", chapter_href(game))
+}
+
+fn chapter_href(game: usize) -> String {
+    format!("game-{}.xhtml", game)
+}
+
+fn package(sheets: &[Sheet], title: &str) -> String {
+    let mut manifest = String::new();
+    let mut spine = String::new();
+    for index in 0..sheets.len() {
+        let game = index + 1;
+        manifest += &format!("<item id=\"game-{}\" href=\"{}\" media-type=\"application/xhtml+xml\" properties=\"svg\"/>\n",
+            game, chapter_href(game));
+        spine += &format!("<itemref idref=\"game-{}\"/>\n", game);
+    }
+    format!("<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" unique-identifier=\"identifier\">
+<metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">
+<dc:identifier id=\"identifier\">urn:pgn2pdf:{}</dc:identifier>
+<dc:title>{}</dc:title>
+<dc:language>en</dc:language>
+<meta property=\"dcterms:modified\">{}</meta>
+</metadata>
+<manifest>
+<item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>
+<item id=\"style\" href=\"style.css\" media-type=\"text/css\"/>
+{}</manifest>
+<spine>
+{}</spine>
+</package>
+", identifier(sheets), escape(title), modification_date(), manifest, spine)
+}
+
+fn navigation(sheets: &[Sheet], title: &str) -> String {
+    let mut items = String::new();
+    for (index, sheet) in sheets.iter().enumerate() {
+        items += &format!("<li><a href=\"{}\">{}</a></li>\n", chapter_href(index + 1), escape(&sheet.title));
+    }
+    page(title, &format!("<nav epub:type=\"toc\" id=\"toc\">\n<h1>{}</h1>\n<ol>\n{}</ol>\n</nav>", escape(title), items))
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!("<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<!DOCTYPE html>
+<html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\" xml:lang=\"en\" lang=\"en\">
+<head>
+<meta charset=\"utf-8\"/>
+<title>{}</title>
+<link rel=\"stylesheet\" type=\"text/css\" href=\"style.css\"/>
+</head>
+<body>
+{}
+</body>
+</html>
+", escape(title), body)
+}
+
+/// Get an identifier derived from the games.
+fn identifier(sheets: &[Sheet]) -> String {
+    let mut hasher = DefaultHasher::new();
+    for sheet in sheets {
+        sheet.title.hash(&mut hasher);
+        sheet.position.fen().hash(&mut hasher);
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Get the current date in the format required by `dcterms:modified`.
+fn modification_date() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((seconds / 86400) as i64);
+    let time = seconds % 86400;
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, time / 3600, time / 60 % 60, time % 60)
+}
+
+/// Convert a number of days since 1970-01-01 to a date of the Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn zip_error(error: ZipError) -> Error {
+    match error {
+        ZipError::Io(error) => Error::from(error),
+        error => Error::from(io::Error::new(io::ErrorKind::Other, error.to_string())),
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! Header of a played game, made from its tags.
+
+use std::fmt::{self, Display, Formatter};
+
+/// Player of a game.
+pub struct Player {
+    pub name: String,
+    /// FIDE title, like `GM`.
+    pub title: Option<String>,
+    pub elo: Option<String>,
+}
+
+impl Player {
+    fn new(tags: &[(String, String)], color: &str) -> Option<Self> {
+        tag(tags, color).map(|name| Player {
+            name: name,
+            title: tag(tags, &format!("{}Title", color)),
+            elo: tag(tags, &format!("{}Elo", color)).and_then(|elo|
+                if elo == "0" {
+                    None
+                }
+                else {
+                    Some(elo)
+                }
+            ),
+        })
+    }
+}
+
+impl Display for Player {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if let Some(ref title) = self.title {
+            try!(write!(formatter, "{} ", title));
+        }
+        try!(write!(formatter, "{}", self.name));
+        if let Some(ref elo) = self.elo {
+            try!(write!(formatter, " ({})", elo));
+        }
+        Ok(())
+    }
+}
+
+/// Known information about a game, the unknown tags being `None`.
+pub struct Header {
+    pub white: Option<Player>,
+    pub black: Option<Player>,
+    pub event: Option<String>,
+    pub site: Option<String>,
+    /// Date, without its unknown parts.
+    pub date: Option<String>,
+    pub round: Option<String>,
+    pub result: Option<String>,
+    pub eco: Option<String>,
+    pub time_control: Option<String>,
+    pub annotator: Option<String>,
+}
+
+impl Header {
+    /// Create the header from the tags, `None` if no tag is known.
+    pub fn new(tags: &[(String, String)]) -> Option<Self> {
+        let header = Header {
+            white: Player::new(tags, "White"),
+            black: Player::new(tags, "Black"),
+            event: tag(tags, "Event"),
+            site: tag(tags, "Site"),
+            date: tag(tags, "Date").and_then(|date| format_date(&date)),
+            round: tag(tags, "Round"),
+            result: tag(tags, "Result").and_then(|result|
+                if result == "*" {
+                    None
+                }
+                else {
+                    Some(result)
+                }
+            ),
+            eco: tag(tags, "ECO"),
+            time_control: tag(tags, "TimeControl"),
+            annotator: tag(tags, "Annotator"),
+        };
+        if header.lines().is_empty() {
+            None
+        }
+        else {
+            Some(header)
+        }
+    }
+
+    /// Remove the players from the header, to show them elsewhere, like in the title.
+    pub fn take_players(&mut self) -> Option<String> {
+        let players: Vec<_> = self.white.take().into_iter().chain(self.black.take())
+            .map(|player| player.to_string())
+            .collect();
+        if players.is_empty() {
+            None
+        }
+        else {
+            Some(players.join(" - "))
+        }
+    }
+
+    /// Get the lines of text of the header: the players, where and when the game was played, and the other details.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![];
+        let players: Vec<_> = self.white.iter().chain(self.black.iter())
+            .map(|player| player.to_string())
+            .collect();
+        if !players.is_empty() {
+            lines.push(players.join(" - "));
+        }
+        let round = self.round.as_ref().map(|round| format!("round {}", round));
+        let place: Vec<_> = [&self.event, &self.site, &round, &self.date].iter()
+            .filter_map(|value| value.as_ref().cloned())
+            .collect();
+        if !place.is_empty() {
+            lines.push(place.join(", "));
+        }
+        let details: Vec<_> = [("Result", &self.result), ("ECO", &self.eco), ("Time control", &self.time_control),
+                ("Annotator", &self.annotator)].iter()
+            .filter_map(|&(name, value)| value.as_ref().map(|value| format!("{}: {}", name, value)))
+            .collect();
+        if !details.is_empty() {
+            lines.push(details.join(" · "));
+        }
+        lines
+    }
+}
+
+/// Get the value of a tag, `None` if it is missing or unknown.
+fn tag(tags: &[(String, String)], name: &str) -> Option<String> {
+    tags.iter()
+        .find(|&&(ref tag, _)| tag == name)
+        .map(|&(_, ref value)| value.trim().to_string())
+        .and_then(|value|
+            if value.is_empty() || value == "?" || value == "-" {
+                None
+            }
+            else {
+                Some(value)
+            }
+        )
+}
+
+/// Remove the unknown parts of a date like `2016.05.??`.
+fn format_date(date: &str) -> Option<String> {
+    let parts: Vec<_> = date.split('.')
+        .take_while(|part| !part.contains('?'))
+        .collect();
+    if parts.is_empty() {
+        None
+    }
+    else {
+        Some(parts.join("."))
+    }
+}
@@ -0,0 +1,188 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! HTML backend, producing a standalone page.
+
+use super::{Line, Move, Note, Sheet, Variations, svg};
+use super::svg::SvgOptions;
+
+/// Size of the squares of the diagrams of the foot-notes, in pixels.
+const NOTE_SQUARE_SIZE: f32 = 20.0;
+
+/// Style sheet of the pages.
+pub const STYLE: &'static str = "
+body {
+    color: #333333;
+    font-family: sans-serif;
+    margin: 2em auto;
+    max-width: 50em;
+    text-align: justify;
+}
+h4, h5 {
+    text-align: center;
+}
+.diagram {
+    text-align: center;
+}
+.header {
+    font-size: small;
+    text-align: center;
+}
+.variations {
+    border-collapse: collapse;
+    width: 100%;
+}
+.variations td, .variations th {
+    padding: 3px;
+    text-align: left;
+}
+.variations tr.white td, .variations tr.white th {
+    padding-top: 1em;
+}
+.notes {
+    padding-left: 2em;
+}
+.notes .back {
+    text-decoration: none;
+}
+";
+
+/// Render the sheets of the games to a standalone HTML page titled `title`.
+pub fn render(sheets: &[Sheet], title: &str, svg_options: &SvgOptions) -> String {
+    let games: Vec<_> = sheets.iter().enumerate()
+        .map(|(index, sheet)| render_game(sheet, index + 1, svg_options))
+        .collect();
+    format!("<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{}</title>
+<style>{}</style>
+</head>
+<body>
+{}
+</body>
+</html>
+", escape(title), STYLE, games.join("\n<hr>\n"))
+}
+
+/// Render the sheet of the `game`th game to a `section` element.
+pub fn render_game(sheet: &Sheet, game: usize, svg_options: &SvgOptions) -> String {
+    let moves: Vec<_> = sheet.moves.iter()
+        .map(|game_move| format_move(game_move, game))
+        .collect();
+    let diagram = svg::render(&sheet.position, &sheet.highlights, &sheet.arrows, svg_options);
+    let header =
+        match sheet.header {
+            Some(ref header) => {
+                let lines: Vec<_> = header.lines().iter().map(|line| escape(line)).collect();
+                format!("<p class=\"header\">{}</p>\n", lines.join("<br/>\n"))
+            },
+            None => String::new(),
+        };
+    let mut result = format!("<section class=\"game\" id=\"game{}\">\n<h4>{}</h4>\n{}<div class=\"diagram\">\n{}</div>\n<h5><strong>{}</strong></h5>\n",
+        game, escape(&sheet.title), header, diagram, moves.join(" "));
+    if let Some(ref variations) = sheet.variations {
+        result += &format_variations(variations, game);
+    }
+    result += &format_notes(&sheet.notes, game, svg_options);
+    result += "</section>";
+    result
+}
+
+fn format_move(game_move: &Move, game: usize) -> String {
+    let mut string = escape(&game_move.text);
+    for note in &game_move.notes {
+        string += &format!("<sup id=\"game{}-ref{}\"><a href=\"#game{}-note{}\">{}</a></sup>", game, note, game, note,
+            note);
+    }
+    string
+}
+
+fn format_row(moves: &[Move], columns: usize, game: usize) -> String {
+    let mut cells: Vec<_> = moves.iter()
+        .map(|game_move| format!("<td>{}</td>", format_move(game_move, game)))
+        .collect();
+    while cells.len() < columns {
+        cells.push("<td></td>".to_string());
+    }
+    cells.concat()
+}
+
+fn format_line(line: &Line, number: usize, columns: usize, game: usize) -> String {
+    format!("<tr class=\"white\"><th rowspan=\"2\">{}</th>{}</tr>\n<tr class=\"black\">{}</tr>\n", number,
+        format_row(&line.white, columns, game), format_row(&line.black, columns, game))
+}
+
+fn format_variations(variations: &Variations, game: usize) -> String {
+    let mut result = "<table class=\"variations\">\n<tr><th></th>".to_string();
+    for num in variations.first_number .. variations.first_number + variations.columns {
+        result += &format!("<th>{}</th>", num);
+    }
+    result += "</tr>\n";
+    for (index, line) in variations.lines.iter().enumerate() {
+        result += &format_line(line, index + 1, variations.columns, game);
+    }
+    result += "</table>\n";
+    result
+}
+
+fn format_note(note: &Note, game: usize, svg_options: &SvgOptions) -> String {
+    match *note {
+        Note::Comment(ref comment) => escape(comment),
+        Note::Diagram(ref comment, ref position) => {
+            let svg_options = SvgOptions {
+                square_size: NOTE_SQUARE_SIZE,
+                .. svg_options.clone()
+            };
+            format!("{}\n<div>\n{}</div>", escape(comment), svg::render(position, &[], &[], &svg_options))
+        },
+        Note::Moves(ref moves) => {
+            let moves: Vec<_> = moves.iter()
+                .map(|game_move| format_move(game_move, game))
+                .collect();
+            moves.join(" ")
+        },
+    }
+}
+
+fn format_notes(notes: &[Note], game: usize, svg_options: &SvgOptions) -> String {
+    if notes.is_empty() {
+        String::new()
+    }
+    else {
+        let notes: Vec<_> = notes.iter().enumerate()
+            .map(|(index, note)|
+                format!("<li id=\"game{}-note{}\">{} <a class=\"back\" href=\"#game{}-ref{}\">↩</a></li>", game, index + 1,
+                    format_note(note, game, svg_options), game, index + 1))
+            .collect();
+        format!("<ol class=\"notes\">\n{}\n</ol>\n", notes.join("\n"))
+    }
+}
+
+/// Escape the characters having a special meaning in HTML.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,159 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! LaTeX backend, using the chessboard package for the diagrams.
+
+use super::{Line, Move, Note, Sheet, Variations, format_inner_move};
+
+/// Render the sheets of the games to a standalone LaTeX document, starting with the `title`, if any.
+pub fn render(sheets: &[Sheet], title: &str) -> String {
+    let title_page =
+        if title.is_empty() {
+            ""
+        }
+        else {
+            "\\maketitle\n\n"
+        };
+    format!("\\documentclass{{article}}
+\\usepackage[utf8]{{inputenc}}
+\\usepackage[T1]{{fontenc}}
+\\usepackage{{chessboard}}
+\\usepackage{{longtable}}
+
+\\title{{{}}}
+\\date{{}}
+
+\\begin{{document}}
+
+{}{}
+\\end{{document}}
+", escape(title), title_page, render_games(sheets))
+}
+
+/// Render the sheets of the games without the preamble, to be included in a larger document.
+/// The chessboard and longtable packages are required.
+pub fn render_games(sheets: &[Sheet]) -> String {
+    let games: Vec<_> = sheets.iter()
+        .map(format_sheet)
+        .collect();
+    games.join("\n\\clearpage\n\n")
+}
+
+fn format_sheet(sheet: &Sheet) -> String {
+    let moves: Vec<_> = sheet.moves.iter()
+        .map(|game_move| format_move(game_move, &sheet.notes))
+        .collect();
+    let header =
+        match sheet.header {
+            Some(ref header) => {
+                let lines: Vec<_> = header.lines().iter().map(|line| escape(line)).collect();
+                format!("\n\\begin{{center}}\n\\small\n{}\n\\end{{center}}\n", lines.join(" \\\\\n"))
+            },
+            None => String::new(),
+        };
+    let mut result = format!("\\section*{{{}}}
+{}
+\\begin{{center}}
+\\chessboard[setfen={{{}}}, showmover=true]
+\\end{{center}}
+
+\\begin{{center}}
+\\textbf{{{}}}
+\\end{{center}}
+", escape(&sheet.title), header, sheet.position.fen(), moves.join(" "));
+    if let Some(ref variations) = sheet.variations {
+        result += "\n";
+        result += &format_variations(variations, &sheet.notes);
+    }
+    result
+}
+
+/// Format a move followed by its comments as foot-notes.
+fn format_move(game_move: &Move, notes: &[Note]) -> String {
+    let mut string = escape(&game_move.text);
+    for &note in &game_move.notes {
+        if let Some(note) = notes.get(note - 1) {
+            string += &format!("\\footnote{{{}}}", format_note(note, notes));
+        }
+    }
+    string
+}
+
+fn format_note(note: &Note, notes: &[Note]) -> String {
+    match *note {
+        Note::Comment(ref comment) => escape(comment.trim()),
+        Note::Diagram(ref comment, ref position) =>
+            format!("{} \\chessboard[tinyboard, showmover=true, setfen={{{}}}]", escape(comment.trim()), position.fen()),
+        Note::Moves(ref moves) => {
+            let moves: Vec<_> = moves.iter()
+                .map(|game_move| format_inner_move(game_move, notes, escape))
+                .collect();
+            moves.join(" ")
+        },
+    }
+}
+
+fn format_row(moves: &[Move], columns: usize, notes: &[Note]) -> String {
+    let mut cells: Vec<_> = moves.iter()
+        .map(|game_move| format_move(game_move, notes))
+        .collect();
+    while cells.len() < columns {
+        cells.push(String::new());
+    }
+    cells.join(" & ")
+}
+
+fn format_line(line: &Line, number: usize, columns: usize, notes: &[Note]) -> String {
+    format!("\\textbf{{{}}} & {} \\\\\n & {} \\\\[1ex]\n", number, format_row(&line.white, columns, notes),
+        format_row(&line.black, columns, notes))
+}
+
+fn format_variations(variations: &Variations, notes: &[Note]) -> String {
+    let numbers: Vec<_> = (variations.first_number .. variations.first_number + variations.columns)
+        .map(|number| number.to_string())
+        .collect();
+    let mut result = format!("{{\\small\n\\begin{{longtable}}{{r*{{{}}}{{l}}}}\n & {} \\\\\n\\hline\n\\endhead\n",
+        variations.columns, numbers.join(" & "));
+    for (index, line) in variations.lines.iter().enumerate() {
+        result += &format_line(line, index + 1, variations.columns, notes);
+    }
+    result += "\\end{longtable}\n}\n";
+    result
+}
+
+/// Escape the characters having a special meaning in LaTeX.
+pub fn escape(text: &str) -> String {
+    let mut result = String::new();
+    for character in text.chars() {
+        match character {
+            '\\' => result += "\\textbackslash{}",
+            '^' => result += "\\textasciicircum{}",
+            '~' => result += "\\textasciitilde{}",
+            '{' | '}' | '$' | '&' | '#' | '_' | '%' => {
+                result.push('\\');
+                result.push(character);
+            },
+            _ => result.push(character),
+        }
+    }
+    result
+}
@@ -0,0 +1,157 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! Markdown backend, using the footnote and table extensions.
+
+use super::{Line, Move, Note, Sheet, Variations, format_inner_move, unicode};
+
+/// Render the sheets of the games to a Markdown document.
+pub fn render(sheets: &[Sheet]) -> String {
+    let games: Vec<_> = sheets.iter().enumerate()
+        .map(|(index, sheet)| {
+            // The labels of the foot-notes must be unique in the document.
+            let prefix =
+                if sheets.len() > 1 {
+                    format!("{}-", index + 1)
+                }
+                else {
+                    String::new()
+                };
+            format_sheet(sheet, &prefix)
+        })
+        .collect();
+    games.join("\n---\n\n")
+}
+
+fn format_sheet(sheet: &Sheet, prefix: &str) -> String {
+    let moves: Vec<_> = sheet.moves.iter()
+        .map(|game_move| format_move(game_move, prefix))
+        .collect();
+    let header =
+        match sheet.header {
+            Some(ref header) => {
+                let lines: Vec<_> = header.lines().iter().map(|line| escape(line)).collect();
+                format!("{}\n\n", lines.join("  \n"))
+            },
+            None => String::new(),
+        };
+    let mut result = format!("#### {}\n\n{}```text\n{}```\n\n**{}**\n", escape(&sheet.title), header,
+        unicode::diagram(&sheet.position), moves.join(" "));
+    if let Some(ref variations) = sheet.variations {
+        result += "\n";
+        result += &format_variations(variations, prefix);
+    }
+    let referenced = referenced_notes(sheet);
+    if !referenced.is_empty() {
+        result += "\n";
+        for number in referenced {
+            result += &format!("[^{}{}]: {}\n", prefix, number, format_note(&sheet.notes[number - 1], &sheet.notes));
+        }
+    }
+    result
+}
+
+/// Get the numbers of the foot-notes referenced by the moves and the variation table, in order. The other ones, of
+/// the moves inside the foot-notes, are shown in their foot-note.
+fn referenced_notes(sheet: &Sheet) -> Vec<usize> {
+    let mut moves: Vec<&Move> = sheet.moves.iter().collect();
+    if let Some(ref variations) = sheet.variations {
+        for line in &variations.lines {
+            moves.extend(line.white.iter().chain(&line.black));
+        }
+    }
+    let mut numbers: Vec<_> = moves.iter()
+        .flat_map(|game_move| game_move.notes.iter().cloned())
+        .filter(|&number| number >= 1 && number <= sheet.notes.len())
+        .collect();
+    numbers.sort();
+    numbers.dedup();
+    numbers
+}
+
+fn format_move(game_move: &Move, prefix: &str) -> String {
+    let mut string = escape(&game_move.text);
+    for note in &game_move.notes {
+        string += &format!("[^{}{}]", prefix, note);
+    }
+    string
+}
+
+fn format_note(note: &Note, notes: &[Note]) -> String {
+    match *note {
+        Note::Comment(ref comment) => escape(comment.trim()),
+        // The diagram is indented to stay in the foot-note.
+        Note::Diagram(ref comment, ref position) =>
+            format!("{}\n\n    ```text\n{}    ```", escape(comment.trim()), indent(&unicode::diagram(position))),
+        Note::Moves(ref moves) => {
+            let moves: Vec<_> = moves.iter()
+                .map(|game_move| format_inner_move(game_move, notes, escape))
+                .collect();
+            moves.join(" ")
+        },
+    }
+}
+
+fn format_row(moves: &[Move], columns: usize, prefix: &str) -> String {
+    let mut cells: Vec<_> = moves.iter()
+        .map(|game_move| format_move(game_move, prefix))
+        .collect();
+    while cells.len() < columns {
+        cells.push(String::new());
+    }
+    cells.join(" | ")
+}
+
+fn format_line(line: &Line, number: usize, columns: usize, prefix: &str) -> String {
+    format!("| **{}** | {} |\n| | {} |\n", number, format_row(&line.white, columns, prefix),
+        format_row(&line.black, columns, prefix))
+}
+
+fn format_variations(variations: &Variations, prefix: &str) -> String {
+    let numbers: Vec<_> = (variations.first_number .. variations.first_number + variations.columns)
+        .map(|number| number.to_string())
+        .collect();
+    let mut result = format!("| | {} |\n|---|{}\n", numbers.join(" | "), "---|".repeat(variations.columns));
+    for (index, line) in variations.lines.iter().enumerate() {
+        result += &format_line(line, index + 1, variations.columns, prefix);
+    }
+    result
+}
+
+/// Escape the characters having a special meaning in Markdown.
+pub fn escape(text: &str) -> String {
+    let mut result = String::new();
+    for character in text.chars() {
+        match character {
+            '\\' | '*' | '_' | '[' | ']' | '|' | '`' | '<' => result.push('\\'),
+            _ => (),
+        }
+        result.push(character);
+    }
+    result
+}
+
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("    {}\n", line))
+        .collect()
+}
@@ -0,0 +1,583 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+/*
+ * TODO: supprimer les annotations dans les commentaires.
+ */
+
+//! Backend-independent content of the documents and the backends rendering it.
+
+pub mod asciidoc;
+pub mod epub;
+pub mod header;
+pub mod html;
+pub mod latex;
+pub mod markdown;
+pub mod pdf;
+mod pieces;
+pub mod svg;
+pub mod text;
+pub mod unicode;
+
+use std::collections::HashMap;
+
+use chess_pgn_parser::{Game, GameMove, Square};
+use chess_pgn_parser::AnnotationSymbol::{Blunder, Brilliant, Dubious, Good, Interesting, Mistake};
+use chess_pgn_parser::Move::{BasicMove, CastleKingside, CastleQueenside};
+use chess_pgn_parser::MoveNumber::White;
+use chess_pgn_parser::Piece::{self, Bishop, King, Knight, Pawn, Queen, Rook};
+
+use board::{ChessGame, Color, parse_square};
+use error::{Error, Result};
+use self::header::Header;
+use self::ShowMoveOptions::*;
+
+/// Number of moves shown in each row of the variation table.
+pub const MOVES_TO_SHOW: usize = 9;
+
+#[derive(PartialEq)]
+pub enum ShowMoveOptions {
+    Normal,
+    WithoutNum,
+}
+
+/// Colour of a highlighted square or of an arrow.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MarkColor {
+    Blue,
+    Green,
+    Red,
+    Yellow,
+}
+
+impl MarkColor {
+    fn from_code(code: char) -> Option<Self> {
+        match code {
+            'B' => Some(MarkColor::Blue),
+            'G' => Some(MarkColor::Green),
+            'R' => Some(MarkColor::Red),
+            'Y' => Some(MarkColor::Yellow),
+            _ => None,
+        }
+    }
+}
+
+/// A highlighted square of the diagram, as given by a `[%csl Gd4]` command in a comment.
+#[derive(Clone, Debug)]
+pub struct Highlight {
+    pub color: MarkColor,
+    pub square: (usize, usize),
+}
+
+/// An arrow of the diagram, as given by a `[%cal Ge2e4]` command in a comment.
+#[derive(Clone, Debug)]
+pub struct Arrow {
+    pub color: MarkColor,
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+}
+
+/// A move followed by the numbers of the foot-notes it refers to.
+pub struct Move {
+    pub text: String,
+    pub notes: Vec<usize>,
+}
+
+impl Move {
+    fn ellipsis() -> Self {
+        Move {
+            text: "…".to_string(),
+            notes: vec![],
+        }
+    }
+}
+
+/// A foot-note.
+pub enum Note {
+    Comment(String),
+    /// Comment followed by a diagram of the position after the move, asked with `[%diagram]`.
+    Diagram(String, ChessGame),
+    /// Continuation of a variation too long to fit in the table.
+    Moves(Vec<Move>),
+}
+
+/// A variation of the table, with the white moves on the first row and the black moves on the second.
+pub struct Line {
+    pub white: Vec<Move>,
+    pub black: Vec<Move>,
+}
+
+pub struct Variations {
+    /// Number of moves shown in each line, the rest being in a foot-note.
+    pub columns: usize,
+    /// Number shown above the first column of moves.
+    pub first_number: usize,
+    /// The main line followed by its alternatives.
+    pub lines: Vec<Line>,
+}
+
+/// Content of the page of a game.
+pub struct Sheet {
+    pub title: String,
+    /// Header of a played game, shown below the title.
+    pub header: Option<Header>,
+    /// Whether the game was played, instead of being a line of a repertoire.
+    pub played: bool,
+    /// Tags of the game, in the order of the PGN file.
+    pub tags: Vec<(String, String)>,
+    /// Position shown in the diagram, right before the first variation.
+    pub position: ChessGame,
+    pub highlights: Vec<Highlight>,
+    pub arrows: Vec<Arrow>,
+    pub moves: Vec<Move>,
+    pub variations: Option<Variations>,
+    /// Foot-notes, numbered from 1.
+    pub notes: Vec<Note>,
+}
+
+impl Sheet {
+    pub fn new(game: &Game) -> Result<Self> {
+        Sheet::with_columns(game, MOVES_TO_SHOW, false)
+    }
+
+    /// Create the sheet of the `game` with `columns` moves in each line of the variation table, at least one.
+    /// With `inline_comments`, the comments of the moves past the table are shown between parentheses after them,
+    /// instead of in their own foot-notes.
+    pub fn with_columns(game: &Game, columns: usize, inline_comments: bool) -> Result<Self> {
+        let columns = columns.max(1);
+        let initial_moves = get_initial_moves(game);
+        let mut position = ChessGame::initial();
+        let mut notes = vec![];
+        let mut moves = vec![];
+        for game_move in &initial_moves {
+            try!(play(&mut position, game_move));
+            moves.push(to_move(game_move, Normal, &position, &mut notes));
+        }
+        let variations = try!(get_variations(game, initial_moves.len(), &position, columns, inline_comments,
+            &mut notes));
+        let mut highlights = vec![];
+        let mut arrows = vec![];
+        if let Some(comment) = initial_moves.last().and_then(|game_move| game_move.comment.as_ref()) {
+            let (_, commands) = parse_comment(comment);
+            for (name, argument) in commands {
+                parse_marks(&name, &argument, &mut highlights, &mut arrows);
+            }
+        }
+        Ok(Sheet {
+            title: get_title(game),
+            header: None,
+            played: false,
+            tags: game.tags.clone(),
+            position: position,
+            highlights: highlights,
+            arrows: arrows,
+            moves: moves,
+            variations: variations,
+            notes: notes,
+        })
+    }
+}
+
+/// Play the move, or return an error with its number and its text, like `3... Re5`, if it is illegal.
+fn play(position: &mut ChessGame, game_move: &GameMove) -> Result<()> {
+    let number = position.fullmove_number();
+    let separator =
+        match position.turn() {
+            Color::White => ".",
+            Color::Black => "...",
+        };
+    if position.play(game_move) {
+        Ok(())
+    }
+    else {
+        Err(Error::IllegalMove {
+            file: None,
+            game: None,
+            move_: format!("{}{} {}", number, separator, move_to_string(game_move, WithoutNum)),
+        })
+    }
+}
+
+fn get_initial_moves(game: &Game) -> Vec<&GameMove> {
+    game.moves.iter()
+        .take_while(|game_move| game_move.variations.is_empty())
+        .collect()
+}
+
+pub fn get_title(game: &Game) -> String {
+    let tags: HashMap<String, String> = game.tags.iter().cloned().collect();
+    match (tags.get("White"), tags.get("Black")) {
+        (Some(opening), Some(variation)) => format!("{} - {}", opening, variation),
+        (None, Some(name)) | (Some(name), None) => name.clone(),
+        (None, None) => String::new(),
+    }
+}
+
+fn is_white_move(game_move: &GameMove) -> bool {
+    if let Some(ref number) = game_move.number {
+        if let White(_) = *number {
+            return true;
+        }
+    }
+    false
+}
+
+/// Extract a line of the variation table. The moves past the `columns` go in a foot-note, numbered before the
+/// foot-notes of their comments, so that the foot-notes are numbered in reading order.
+/// The variations of the moves go in foot-notes too, except the ones of the first move of the `main_line`, which are
+/// the other lines of the table.
+fn extract_variation(moves: &[GameMove], start: &ChessGame, columns: usize, main_line: bool, inline_comments: bool,
+    notes: &mut Vec<Note>) -> Result<Line>
+{
+    let first_move =
+        match moves.first() {
+            Some(first_move) => first_move,
+            None => return Err(Error::Syntax {
+                file: None,
+                game: None,
+                line: None,
+                message: "empty variation".to_string(),
+            }),
+        };
+    let first_move_is_black = !is_white_move(first_move);
+    let mut position = start.clone();
+    let mut first_white_moves = vec![];
+    let mut first_black_moves = vec![];
+    let mut move_num = 0;
+    let mut shown = 0;
+    while shown < moves.len() && move_num < columns {
+        let game_move = &moves[shown];
+        let previous_position = position.clone();
+        try!(play(&mut position, game_move));
+        let mut table_move = to_move(game_move, WithoutNum, &position, notes);
+        if !main_line || shown > 0 {
+            try!(add_variation_notes(game_move, &mut table_move, &previous_position, inline_comments, notes));
+        }
+        if is_white_move(game_move) {
+            first_white_moves.push(table_move);
+        }
+        else {
+            first_black_moves.push(table_move);
+            move_num += 1;
+        }
+        shown += 1;
+    }
+    let rest = &moves[shown..];
+    if !rest.is_empty() {
+        notes.push(Note::Moves(vec![]));
+        let number = notes.len();
+        first_black_moves.last_mut().unwrap().notes.push(number);
+        let variation = try!(note_moves(rest, position, inline_comments, notes));
+        notes[number - 1] = Note::Moves(variation);
+    }
+    // TODO: add ¹ for variations.
+    // TODO: add variation evaluation.
+    if first_move_is_black {
+        first_white_moves.insert(0, Move::ellipsis());
+    }
+    Ok(Line {
+        white: first_white_moves,
+        black: first_black_moves,
+    })
+}
+
+/// Convert the moves of a foot-note, played from `position`. Their comments and their variations go in their own
+/// foot-notes, or between parentheses after them with `inline_comments`.
+fn note_moves(moves: &[GameMove], mut position: ChessGame, inline_comments: bool, notes: &mut Vec<Note>)
+    -> Result<Vec<Move>>
+{
+    let mut result = vec![];
+    for game_move in moves {
+        let previous_position = position.clone();
+        try!(play(&mut position, game_move));
+        let mut note_move = to_move(game_move, Normal, &position, notes);
+        if inline_comments {
+            inline_comment(&mut note_move, notes);
+        }
+        try!(add_variation_notes(game_move, &mut note_move, &previous_position, inline_comments, notes));
+        result.push(note_move);
+    }
+    Ok(result)
+}
+
+/// Add a foot-note with each variation of `game_move`, played from `position`, the one before the move, to the
+/// foot-notes of `converted`.
+fn add_variation_notes(game_move: &GameMove, converted: &mut Move, position: &ChessGame, inline_comments: bool,
+    notes: &mut Vec<Note>) -> Result<()>
+{
+    for variation in &game_move.variations {
+        notes.push(Note::Moves(vec![]));
+        let number = notes.len();
+        converted.notes.push(number);
+        let moves = try!(note_moves(&variation.moves, position.clone(), inline_comments, notes));
+        notes[number - 1] = Note::Moves(moves);
+    }
+    Ok(())
+}
+
+fn get_variations(game: &Game, start_move_num: usize, start: &ChessGame, columns: usize, inline_comments: bool,
+    notes: &mut Vec<Note>) -> Result<Option<Variations>>
+{
+    let start_move =
+        match game.moves.get(start_move_num) {
+            Some(start_move) => start_move,
+            None => return Ok(None),
+        };
+    if start_move.variations.is_empty() {
+        return Ok(None);
+    }
+    let mut lines = vec![try!(extract_variation(&game.moves[start_move_num..], start, columns, true,
+        inline_comments, notes))];
+    for variation in &start_move.variations {
+        lines.push(try!(extract_variation(&variation.moves, start, columns, false, inline_comments, notes)));
+    }
+    Ok(Some(Variations {
+        columns: columns,
+        first_number: start_move_num.saturating_sub(1),
+        lines: lines,
+    }))
+}
+
+/// Split a comment into its text and its embedded commands, like `[%cal Ge2e4]`.
+fn parse_comment(comment: &str) -> (String, Vec<(String, String)>) {
+    let mut text = String::new();
+    let mut commands = vec![];
+    let mut rest = comment;
+    while let Some(start) = rest.find("[%") {
+        match rest[start..].find(']') {
+            Some(end) => {
+                text.push_str(&rest[..start]);
+                let command = rest[start + 2 .. start + end].trim();
+                let (name, argument) =
+                    match command.find(char::is_whitespace) {
+                        Some(index) => (&command[..index], command[index..].trim()),
+                        None => (command, ""),
+                    };
+                commands.push((name.to_string(), argument.to_string()));
+                rest = &rest[start + end + 1..];
+            },
+            None => break,
+        }
+    }
+    text.push_str(rest);
+    if commands.is_empty() {
+        (text, commands)
+    }
+    else {
+        (text.trim().to_string(), commands)
+    }
+}
+
+/// Parse the argument of the `csl` and `cal` commands, like `Gd4,Rf5` and `Ge2e4`.
+fn parse_marks(name: &str, argument: &str, highlights: &mut Vec<Highlight>, arrows: &mut Vec<Arrow>) {
+    for mark in argument.split(',') {
+        let mark = mark.trim();
+        let color = mark.chars().next().and_then(MarkColor::from_code);
+        let squares = mark.get(1..).unwrap_or("");
+        match (name, color, squares.len()) {
+            ("csl", Some(color), 2) =>
+                if let Some(square) = parse_square(squares) {
+                    highlights.push(Highlight {
+                        color: color,
+                        square: square,
+                    });
+                },
+            ("cal", Some(color), 4) =>
+                // The length is in bytes: `get()` returns `None` rather than cutting a multi-byte character, and
+                // such a mark is ignored.
+                if let (Some(from), Some(to)) =
+                    (squares.get(..2).and_then(parse_square), squares.get(2..).and_then(parse_square))
+                {
+                    arrows.push(Arrow {
+                        color: color,
+                        from: from,
+                        to: to,
+                    });
+                },
+            _ => (),
+        }
+    }
+}
+
+/// Convert the move, whose comment goes in the `notes`; `position` is the one after the move.
+fn to_move(game_move: &GameMove, options: ShowMoveOptions, position: &ChessGame, notes: &mut Vec<Note>) -> Move {
+    let mut move_notes = vec![];
+    if let Some(ref comment) = game_move.comment {
+        let (text, commands) = parse_comment(&comment.replace('\n', " "));
+        let diagram = commands.iter().any(|&(ref name, _)| name == "diagram");
+        if diagram {
+            notes.push(Note::Diagram(text, position.clone()));
+            move_notes.push(notes.len());
+        }
+        // Comments made only of commands are not shown.
+        else if !text.is_empty() || commands.is_empty() {
+            notes.push(Note::Comment(text));
+            move_notes.push(notes.len());
+        }
+    }
+    Move {
+        text: move_to_string(game_move, options),
+        notes: move_notes,
+    }
+}
+
+/// Move the comment of `game_move`, the last foot-note, after its text between parentheses.
+/// The diagrams stay in their foot-notes.
+fn inline_comment(game_move: &mut Move, notes: &mut Vec<Note>) {
+    if game_move.notes.last() != Some(&notes.len()) {
+        return;
+    }
+    match notes.pop() {
+        Some(Note::Comment(comment)) => {
+            game_move.notes.pop();
+            if !comment.trim().is_empty() {
+                game_move.text += &format!(" ({})", comment.trim());
+            }
+        },
+        Some(note) => notes.push(note),
+        None => (),
+    }
+}
+
+/// Format a move inside a foot-note, where the comments and the variations are shown between parentheses, without
+/// the diagrams. The texts are escaped with the `escape` function of the backend.
+pub fn format_inner_move(game_move: &Move, notes: &[Note], escape: fn(&str) -> String) -> String {
+    let mut string = escape(&game_move.text);
+    for &note in &game_move.notes {
+        match notes.get(note - 1) {
+            Some(&Note::Comment(ref comment)) | Some(&Note::Diagram(ref comment, _)) if !comment.trim().is_empty() =>
+                string += &format!(" ({})", escape(comment.trim())),
+            Some(&Note::Moves(ref moves)) => {
+                let moves: Vec<_> = moves.iter()
+                    .map(|game_move| format_inner_move(game_move, notes, escape))
+                    .collect();
+                string += &format!(" ({})", moves.join(" "));
+            },
+            _ => (),
+        }
+    }
+    string
+}
+
+pub fn move_to_string(game_move: &GameMove, options: ShowMoveOptions) -> String {
+    let mut string = String::new();
+    if options != WithoutNum {
+        if let Some(White(number)) = game_move.number {
+            string += &format!("{}.", number);
+        }
+    }
+    let mov =
+        match game_move.move_.move_ {
+            BasicMove { ref from, is_capture, ref piece, ref promoted_to, ref to } => {
+                let piece = piece_to_string(piece);
+                let from = square_to_string(from);
+                let symbol =
+                    if is_capture {
+                        "x"
+                    }
+                    else {
+                        ""
+                    };
+                let to = square_to_string(to);
+                let promotion =
+                    if let Some(ref piece) = *promoted_to {
+                        format!("={}", piece_to_string(piece))
+                    }
+                    else {
+                        String::new()
+                    };
+                format!("{}{}{}{}{}", piece, from, symbol, to, promotion)
+            },
+            CastleKingside => "O-O".to_string(),
+            CastleQueenside => "O-O-O".to_string(),
+        };
+    string += &mov;
+    // TODO: support nags.
+    if let Some(ref annotation) = game_move.move_.annotation_symbol {
+        let annotation =
+            match *annotation {
+                Blunder => "??",
+                Brilliant => "!!",
+                Dubious => "?!",
+                Good => "!",
+                Interesting => "!?",
+                Mistake => "?",
+            };
+        string += annotation;
+    }
+    if game_move.move_.is_check {
+        string += "+";
+    }
+    else if game_move.move_.is_checkmate {
+        string += "#";
+    }
+    string
+}
+
+fn piece_to_string(piece: &Piece) -> &str {
+    // TODO: translate in english.
+    match *piece {
+        Bishop => "F",
+        King => "R",
+        Knight => "C",
+        Pawn => "",
+        Queen => "D",
+        Rook => "T",
+    }
+}
+
+fn square_to_string(square: &Square) -> String {
+    format!("{:?}", square).to_lowercase().replace('x', "")
+}
+
+#[cfg(test)]
+mod tests {
+    use chess_pgn_parser::read_games;
+
+    use error::Error;
+    use super::{Sheet, parse_comment, parse_marks, MarkColor};
+
+    #[test]
+    fn empty_variation() {
+        let games = read_games("1. e4 ( ) e5 *\n").unwrap();
+        match Sheet::new(&games[0]) {
+            Err(Error::Syntax { ref message, .. }) => assert_eq!(message, "empty variation"),
+            _ => panic!("an empty variation must be a syntax error"),
+        }
+    }
+
+    #[test]
+    fn parse_marks_next_to_multi_byte_characters() {
+        let (text, commands) = parse_comment("é[%cal Ge2e4,Gée4,Re2é,Bd2d4é]à");
+        assert_eq!(text, "éà");
+        let mut highlights = vec![];
+        let mut arrows = vec![];
+        for (name, argument) in commands {
+            parse_marks(&name, &argument, &mut highlights, &mut arrows);
+        }
+        assert!(highlights.is_empty());
+        assert_eq!(arrows.len(), 1);
+        assert_eq!(arrows[0].color, MarkColor::Green);
+        assert_eq!(arrows[0].from, (4, 6));
+        assert_eq!(arrows[0].to, (4, 4));
+    }
+}
@@ -0,0 +1,79 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! PDF backend, calling asciidoctor-pdf on the output of the AsciiDoc backend.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+use error::{Error, Result};
+
+const PROGRAM: &'static str = "asciidoctor-pdf";
+
+/// Typeset the AsciiDoc file `input` to the PDF file `output`.
+/// Return the warnings of asciidoctor-pdf, one per line.
+pub fn run_asciidoctor(input: &Path, output: &Path) -> Result<Vec<String>> {
+    let result = Command::new(PROGRAM)
+        .arg(input)
+        .arg("-o")
+        .arg(output)
+        .output();
+    let output = match result {
+        Ok(output) => output,
+        Err(error) => {
+            let message =
+                if error.kind() == io::ErrorKind::NotFound {
+                    "not found, please install it".to_string()
+                }
+                else {
+                    error.to_string()
+                };
+            return Err(Error::Backend {
+                program: PROGRAM.to_string(),
+                status: None,
+                message: message,
+            });
+        },
+    };
+    let errors = String::from_utf8_lossy(&output.stderr);
+    if output.status.success() {
+        Ok(errors.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+    else {
+        let message =
+            if output.status.code().is_none() {
+                format!("killed by a signal\n{}", errors)
+            }
+            else {
+                errors.into_owned()
+            };
+        Err(Error::Backend {
+            program: PROGRAM.to_string(),
+            status: output.status.code(),
+            message: message,
+        })
+    }
+}
@@ -0,0 +1,265 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! Outlines of the chess pieces of the SVG diagrams, made from the chess symbols of DejaVu Sans, whose license is in
+//! LICENSE-DejaVu. They are SVG paths in a square of 100 units, with the y axis going down.
+
+use chess_pgn_parser::Piece::{self, Bishop, King, Knight, Pawn, Queen, Rook};
+
+use board::Color::{self, Black, White};
+
+/// Outline of a piece.
+pub struct Outline {
+    /// Path of the piece, filled with black.
+    pub path: &'static str,
+    /// Path of the inside of a white piece, filled with white under the outline so that the square does not show
+    /// through.
+    pub inside: Option<&'static str>,
+}
+
+/// Get the outline of a piece.
+pub fn outline(piece: &(Color, Piece)) -> Outline {
+    let (path, inside) =
+        match *piece {
+            (White, King) => (WHITE_KING, Some(WHITE_KING_INSIDE)),
+            (White, Queen) => (WHITE_QUEEN, Some(WHITE_QUEEN_INSIDE)),
+            (White, Rook) => (WHITE_ROOK, Some(WHITE_ROOK_INSIDE)),
+            (White, Bishop) => (WHITE_BISHOP, Some(WHITE_BISHOP_INSIDE)),
+            (White, Knight) => (WHITE_KNIGHT, Some(WHITE_KNIGHT_INSIDE)),
+            (White, Pawn) => (WHITE_PAWN, Some(WHITE_PAWN_INSIDE)),
+            (Black, King) => (BLACK_KING, None),
+            (Black, Queen) => (BLACK_QUEEN, None),
+            (Black, Rook) => (BLACK_ROOK, None),
+            (Black, Bishop) => (BLACK_BISHOP, None),
+            (Black, Knight) => (BLACK_KNIGHT, None),
+            (Black, Pawn) => (BLACK_PAWN, None),
+        };
+    Outline {
+        path: path,
+        inside: inside,
+    }
+}
+
+const WHITE_KING: &'static str =
+    "M46 37.4Q46 37.2 46.1 37.1Q44.1 34.6 41.2 33Q37 30.6 32.5 30.6Q28 30.6 23.8 33Q19.5 35.3 17.1 39.6\
+    Q14.7 43.8 14.7 48.4Q14.7 53.1 17.1 57.3Q19.4 61.5 23.7 63.8Q24.9 64.6 26.3 65.1L46 65.1L46 37.4ZM54.1 65.1\
+    L73.7 65.1Q75.1 64.6 76.4 63.8Q80.6 61.5 83 57.3Q85.3 53.1 85.3 48.4Q85.3 43.8 82.9 39.6Q80.5 35.3 76.3 33\
+    Q72.1 30.6 67.5 30.6Q63.1 30.6 58.9 33Q55.9 34.6 53.9 37.1Q54 37.2 54.1 37.4L54.1 65.1ZM28.3 71L28.3 88.4\
+    L71.8 88.4L71.8 71L28.3 71ZM23.6 93L23.6 71Q21.1 70.2 19 69Q15 65.9 11.9 60.2Q8.8 54.6 8.8 48.4\
+    Q8.8 42.2 11.9 36.6Q15.1 30.9 20.8 27.9Q26.5 24.8 32.5 24.8Q37.1 24.8 41.4 26.6L41.4 26.5\
+    Q41.4 24.4 42.5 22.6Q43.5 20.8 45.3 19.8Q46.1 19.4 47 19.1L47 16L40.8 16L40.8 12L47 12L47 7L53 7L53 12\
+    L59.3 12L59.3 16L53 16L53 19.1Q54 19.4 54.7 19.8Q56.5 20.8 57.6 22.6Q58.6 24.4 58.6 26.5L58.6 26.6\
+    Q63 24.8 67.5 24.8Q73.5 24.8 79.2 27.9Q84.9 30.9 88.1 36.6Q91.2 42.2 91.2 48.4Q91.2 54.6 88.1 60.2\
+    Q85 65.9 81.1 69Q78.9 70.2 76.5 71L76.5 93L23.6 93ZM44.7 26.9Q44.7 28.4 45.4 29.6Q46.1 30.9 47.4 31.6\
+    Q48.7 32.3 50 32.3Q51.4 32.3 52.7 31.6Q54 30.9 54.7 29.6Q55.3 28.4 55.3 26.9Q55.3 25.6 54.7 24.3\
+    Q54 23 52.7 22.3Q51.4 21.7 50 21.7Q48.7 21.7 47.4 22.3Q46.1 23 45.4 24.3Q44.7 25.6 44.7 26.9Z";
+
+const WHITE_KING_INSIDE: &'static str =
+    "M23.6 93L23.6 71Q21.1 70.2 19 69Q15 65.9 11.9 60.2Q8.8 54.6 8.8 48.4Q8.8 42.2 11.9 36.6Q15.1 30.9 20.8 27.9\
+    Q26.5 24.8 32.5 24.8Q37.1 24.8 41.4 26.6L41.4 26.5Q41.4 24.4 42.5 22.6Q43.5 20.8 45.3 19.8Q46.1 19.4 47 19.1\
+    L47 16L40.8 16L40.8 12L47 12L47 7L53 7L53 12L59.3 12L59.3 16L53 16L53 19.1Q54 19.4 54.7 19.8\
+    Q56.5 20.8 57.6 22.6Q58.6 24.4 58.6 26.5L58.6 26.6Q63 24.8 67.5 24.8Q73.5 24.8 79.2 27.9Q84.9 30.9 88.1 36.6\
+    Q91.2 42.2 91.2 48.4Q91.2 54.6 88.1 60.2Q85 65.9 81.1 69Q78.9 70.2 76.5 71L76.5 93L23.6 93Z";
+
+const WHITE_QUEEN: &'static str =
+    "M16.4 34.4Q10.9 34.3 10.2 28.8Q10.2 27.2 11.1 25.8Q11.9 24.3 13.4 23.5Q15 22.7 16.7 22.7Q18.3 22.7 19.8 23.5\
+    Q21.4 24.3 22.2 25.8Q23 27.2 23 28.8Q22.8 32.5 19.5 33.8L33.2 62.9L30.2 22.5Q25.5 22.5 24.4 17.2\
+    Q24.4 15.6 25.3 14.1Q26.1 12.7 27.6 11.9Q29.1 11.1 30.9 11.1Q32.5 11.1 34 11.9Q35.6 12.7 36.4 14.1\
+    Q37.2 15.6 37.2 17.2Q37.1 19.8 34.5 21.7L45.3 64.7L47.8 17.6Q44 15.9 43.8 13.1Q43.8 11.5 44.7 10\
+    Q45.6 8.5 47.1 7.7Q48.6 7 50.2 7Q51.8 7 53.4 7.7Q54.9 8.5 55.7 10Q56.6 11.5 56.6 13.1Q56.4 16.2 52.6 17.6\
+    L54.8 64.8L65.4 22.3Q62.8 19.6 62.8 17.2Q62.8 15.6 63.6 14.2Q64.4 12.7 66 11.9Q67.5 11.1 69.1 11.1\
+    Q70.8 11.1 72.4 11.9Q73.9 12.7 74.7 14.2Q75.6 15.6 75.6 17.2Q74.9 22.1 70 23L64.7 62.9L79.5 33.3\
+    Q77 31.3 77 28.8Q77 27.2 77.9 25.8Q78.7 24.3 80.2 23.5Q81.7 22.8 83.3 22.8Q85.1 22.8 86.6 23.5\
+    Q88.1 24.3 88.9 25.8Q89.8 27.2 89.8 28.8Q89 34.2 83.3 34.2L74.1 60.9L74.1 93L24.6 93L24.6 60.9L16.4 34.4Z\
+    M70.9 77.5L70.9 64.5L60.5 68.9L39.6 68.9L29.1 65.3L29.1 77.5L70.9 77.5ZM29.1 80.2L29.1 89.7L70.9 89.7\
+    L70.9 80.2L29.1 80.2ZM26.7 60.7L31.1 63.1L17.8 32.9Q18.2 32.7 18.6 32.5Q19.6 31.9 20.2 30.9\
+    Q20.8 29.9 20.8 28.8Q20.8 27.6 20.2 26.6Q19.6 25.6 18.6 25.1Q17.6 24.5 16.5 24.5Q15.5 24.5 14.4 25.1\
+    Q13.4 25.6 12.8 26.6Q12.2 27.6 12.2 28.8Q12.2 29.9 12.8 30.9Q13.4 31.9 14.4 32.5Q15.5 33 16.5 33\
+    Q16.9 33 17.4 33L26.7 60.7ZM35.2 62.9L43 64.7L32.3 21Q32.6 20.9 32.9 20.7Q33.9 20.1 34.4 19.1Q35 18.1 35 17\
+    Q35 15.9 34.4 14.9Q33.9 13.8 32.9 13.3Q31.8 12.7 30.8 12.7Q29.7 12.7 28.7 13.3Q27.7 13.8 27.1 14.9\
+    Q26.5 15.9 26.5 17Q26.5 18.1 27.1 19.1Q27.6 20.1 28.7 20.7Q29.7 21.3 30.8 21.3Q31.2 21.3 31.7 21.2L35.2 62.9\
+    ZM56.3 64.3L62.9 62.7L68.5 21.3Q68.7 21.3 69 21.3Q70.1 21.3 71.2 20.7Q72.2 20.2 72.8 19.2\
+    Q73.3 18.2 73.3 17.1Q73.3 16 72.7 14.9Q72.1 13.9 71.1 13.4Q70.1 12.8 69 12.8Q68 12.8 67 13.4\
+    Q65.9 13.9 65.3 14.9Q64.8 16 64.8 17.1Q64.8 18.2 65.3 19.2Q65.9 20.2 67 20.7Q67.4 21 67.8 21.1L56.3 64.3Z\
+    M47.2 64.7L53 64.7L50.2 16.8Q51.3 16.8 52.2 16.2Q53.2 15.6 53.8 14.6Q54.4 13.6 54.4 12.5Q54.4 11.4 53.8 10.4\
+    Q53.2 9.4 52.2 8.8Q51.2 8.2 50.1 8.2Q49.1 8.2 48 8.8Q47 9.4 46.4 10.4Q45.9 11.4 45.9 12.5\
+    Q45.9 13.6 46.4 14.6Q47 15.6 48 16.2Q48.9 16.7 49.7 16.8L47.2 64.7ZM66.8 63.1L72.8 60.7L82.3 32.5\
+    Q82.9 32.7 83.6 32.7Q84.7 32.7 85.7 32.1Q86.7 31.5 87.3 30.5Q87.8 29.5 87.8 28.4Q87.8 27.3 87.2 26.3\
+    Q86.6 25.2 85.6 24.7Q84.7 24.1 83.6 24.1Q82.4 24.1 81.4 24.7Q80.4 25.2 79.9 26.3Q79.3 27.3 79.3 28.4\
+    Q79.3 29.5 79.9 30.5Q80.4 31.5 81.4 32.1Q81.7 32.2 81.8 32.4L66.8 63.1Z";
+
+const WHITE_QUEEN_INSIDE: &'static str =
+    "M16.4 34.4Q10.9 34.3 10.2 28.8Q10.2 27.2 11.1 25.8Q11.9 24.3 13.4 23.5Q15 22.7 16.7 22.7Q18.3 22.7 19.8 23.5\
+    Q21.4 24.3 22.2 25.8Q23 27.2 23 28.8Q22.8 32.5 19.5 33.8L33.2 62.9L30.2 22.5Q25.5 22.5 24.4 17.2\
+    Q24.4 15.6 25.3 14.1Q26.1 12.7 27.6 11.9Q29.1 11.1 30.9 11.1Q32.5 11.1 34 11.9Q35.6 12.7 36.4 14.1\
+    Q37.2 15.6 37.2 17.2Q37.1 19.8 34.5 21.7L45.3 64.7L47.8 17.6Q44 15.9 43.8 13.1Q43.8 11.5 44.7 10\
+    Q45.6 8.5 47.1 7.7Q48.6 7 50.2 7Q51.8 7 53.4 7.7Q54.9 8.5 55.7 10Q56.6 11.5 56.6 13.1Q56.4 16.2 52.6 17.6\
+    L54.8 64.8L65.4 22.3Q62.8 19.6 62.8 17.2Q62.8 15.6 63.6 14.2Q64.4 12.7 66 11.9Q67.5 11.1 69.1 11.1\
+    Q70.8 11.1 72.4 11.9Q73.9 12.7 74.7 14.2Q75.6 15.6 75.6 17.2Q74.9 22.1 70 23L64.7 62.9L79.5 33.3\
+    Q77 31.3 77 28.8Q77 27.2 77.9 25.8Q78.7 24.3 80.2 23.5Q81.7 22.8 83.3 22.8Q85.1 22.8 86.6 23.5\
+    Q88.1 24.3 88.9 25.8Q89.8 27.2 89.8 28.8Q89 34.2 83.3 34.2L74.1 60.9L74.1 93L24.6 93L24.6 60.9L16.4 34.4Z";
+
+const WHITE_ROOK: &'static str =
+    "M41.9 7L57.8 7L57.8 15.9L67.6 15.9L67.6 7L83.1 7L83.1 16.3L76.5 16.3L76.5 24.6L71.2 33.2L71.2 66.8L76.5 75.4\
+    L76.5 83.6L83.1 83.6L83.1 93L16.9 93L16.9 83.6L23.5 83.6L23.5 75.4L28.8 66.7L28.9 66.7L28.9 33.3L28.8 33.3\
+    L23.5 24.6L23.5 16.3L16.9 16.3L16.9 7L32.1 7L32.1 15.9L41.9 15.9L41.9 7ZM80 89.8L80 86.6L20.1 86.6L20.1 89.8\
+    L80 89.8ZM68.5 32.2L73.2 24.6L73.2 19.2L26.9 19.2L26.9 24.6L31.6 32.2L68.5 32.2ZM31.6 69.5L27.4 76.2\
+    L27.4 83.6L72.7 83.6L72.7 76.2L68.4 69.5L31.6 69.5ZM68.4 66.6L68.4 35.2L68.5 35.1L31.6 35.1L31.6 66.6\
+    L68.4 66.6Z";
+
+const WHITE_ROOK_INSIDE: &'static str =
+    "M41.9 7L57.8 7L57.8 15.9L67.6 15.9L67.6 7L83.1 7L83.1 16.3L76.5 16.3L76.5 24.6L71.2 33.2L71.2 66.8L76.5 75.4\
+    L76.5 83.6L83.1 83.6L83.1 93L16.9 93L16.9 83.6L23.5 83.6L23.5 75.4L28.8 66.7L28.9 66.7L28.9 33.3L28.8 33.3\
+    L23.5 24.6L23.5 16.3L16.9 16.3L16.9 7L32.1 7L32.1 15.9L41.9 15.9L41.9 7Z";
+
+const WHITE_BISHOP: &'static str =
+    "M38.3 69.8Q36.5 66.7 36.5 63.5Q36.5 60 38.4 57Q38.8 56 39.5 55.2Q36 53 34 49.4Q31.6 45.2 31.5 40.6L31.5 40.4\
+    Q31.6 35.8 34 31.6Q36.4 27.3 40.9 24.9Q42.5 24.1 44.3 23.5Q42.8 22.5 41.8 20.8Q40.5 18.6 40.5 16.2\
+    Q40.5 13.7 41.8 11.6Q43.1 9.4 45.3 8.2Q47.6 7 50 7Q52.4 7 54.6 8.2Q56.8 9.4 58.1 11.6Q59.4 13.7 59.4 16.2\
+    Q59.4 18.6 58.2 20.8Q57.3 22.5 55.8 23.6Q57.4 24.1 59.1 24.9Q63.5 27.3 66 31.6Q68.5 35.8 68.5 40.4L68.5 40.6\
+    Q68.5 45.2 66 49.4Q63.9 52.9 60.6 55.1Q61.2 56 61.7 57Q63.5 60 63.5 63.5Q63.5 66.8 61.6 69.9\
+    Q61.5 70.1 61.4 70.3L77.6 93L22.4 93L38 69.8L38.3 69.8ZM53.4 50.6L53.4 43.4L63.3 43.4L63.3 37.2L53.4 37.2\
+    L53.4 30.1L46.6 30.1L46.6 37.2L36.7 37.2L36.7 43.4L46.6 43.4L46.6 50.6L53.4 50.6ZM55.9 69.8\
+    Q59.4 67.2 59.4 63Q59.4 60.5 58.1 58.3Q56.8 56 54.6 54.9Q52.4 53.7 49.9 53.7Q47.5 53.7 45.3 54.9\
+    Q43.1 56 41.8 58.3Q40.5 60.5 40.5 63Q40.5 66.7 44.1 69.8L31.4 89.3L68.7 89.3L55.9 69.8ZM50 9.7\
+    Q48.3 9.7 46.8 10.5Q45.3 11.3 44.5 12.8Q43.6 14.3 43.6 16Q43.6 17.6 44.5 19.2Q45.3 20.7 46.8 21.5\
+    Q48.3 22.3 50 22.3Q51.7 22.3 53.2 21.5Q54.8 20.7 55.6 19.2Q56.4 17.6 56.4 16Q56.4 14.3 55.6 12.8\
+    Q54.7 11.3 53.2 10.5Q51.6 9.7 50 9.7Z";
+
+const WHITE_BISHOP_INSIDE: &'static str =
+    "M38.3 69.8Q36.5 66.7 36.5 63.5Q36.5 60 38.4 57Q38.8 56 39.5 55.2Q36 53 34 49.4Q31.6 45.2 31.5 40.6L31.5 40.4\
+    Q31.6 35.8 34 31.6Q36.4 27.3 40.9 24.9Q42.5 24.1 44.3 23.5Q42.8 22.5 41.8 20.8Q40.5 18.6 40.5 16.2\
+    Q40.5 13.7 41.8 11.6Q43.1 9.4 45.3 8.2Q47.6 7 50 7Q52.4 7 54.6 8.2Q56.8 9.4 58.1 11.6Q59.4 13.7 59.4 16.2\
+    Q59.4 18.6 58.2 20.8Q57.3 22.5 55.8 23.6Q57.4 24.1 59.1 24.9Q63.5 27.3 66 31.6Q68.5 35.8 68.5 40.4L68.5 40.6\
+    Q68.5 45.2 66 49.4Q63.9 52.9 60.6 55.1Q61.2 56 61.7 57Q63.5 60 63.5 63.5Q63.5 66.8 61.6 69.9\
+    Q61.5 70.1 61.4 70.3L77.6 93L22.4 93L38 69.8L38.3 69.8Z";
+
+const WHITE_KNIGHT: &'static str =
+    "M43.1 20.8Q43.3 20.3 43.3 19.7Q43.3 18.5 42.7 17.3Q42.1 16 42.1 15.2Q42.1 12.4 46.1 7Q53.1 13 53.1 18.9\
+    Q53.1 20.4 52.6 21.9Q80.8 40.5 83.4 93L27.6 93Q26.9 89.8 26.9 86.4Q26.9 77.2 38.2 68.7Q49.5 60.2 49.5 56.6\
+    Q49.5 56.6 49.5 56.2Q44.4 62.8 41.1 63Q38.5 71.2 29.4 72.2L28.9 70.9L26.4 72.6Q18.4 70 16.6 65.4\
+    Q21.1 51.1 21.1 40.1Q21.1 38.3 21 36.7Q23.7 30.5 27.9 23.4Q26.7 21.6 26.5 21.1Q26.3 20.5 26.3 20.2\
+    Q26.3 18.7 28.2 12.3Q34.1 13.9 37.2 20.8L43.1 20.8ZM27.9 36.7Q30.1 33.1 35.6 33.1L35.9 33.1\
+    Q33.4 35.7 33.4 37.8Q33.4 40.6 29.5 40.6L28.3 40.6Q27.8 40.6 27.6 38.8L27.6 38L27.9 36.7ZM27.2 61L27.6 63\
+    Q27.6 66 23.5 66.8L22.9 66.8Q21.3 66.8 21.3 65.1L21.3 63.8Q23.7 61 27.2 61ZM36.1 21.9Q34.1 16.4 29.6 14.2\
+    Q28.2 17.8 28.2 19.9Q28.2 21.5 29 22.4Q29.9 23.4 29.9 24.4Q29.9 25.3 29.2 26.2Q25.9 31.8 23.3 37.5\
+    Q23.6 40 23.6 42.5Q23.6 53.9 19.3 64.9Q20.7 69.3 26.7 70.6L32.2 66.5L30.2 70.8Q38 70.2 39.7 61.9\
+    Q45.7 61.7 50.4 51.7Q51 53.4 51 55.2Q51 62.1 40.1 70Q29.1 77.9 29.1 85.1Q29.1 88.2 29.8 91.2L80.1 91.2\
+    Q77.8 43.8 49.4 22Q49.5 22 49.5 21.9L49.3 21.9Q51 21.4 51 18.9Q51 18.2 50.7 15.9Q50.3 13.7 45.9 9.4\
+    Q44.1 13.7 44.1 16Q44.1 17.1 44.6 18.1Q45.1 19 45.1 20.1Q45.1 21.1 44.7 21.9L36.1 21.9Z";
+
+const WHITE_KNIGHT_INSIDE: &'static str =
+    "M43.1 20.8Q43.3 20.3 43.3 19.7Q43.3 18.5 42.7 17.3Q42.1 16 42.1 15.2Q42.1 12.4 46.1 7Q53.1 13 53.1 18.9\
+    Q53.1 20.4 52.6 21.9Q80.8 40.5 83.4 93L27.6 93Q26.9 89.8 26.9 86.4Q26.9 77.2 38.2 68.7Q49.5 60.2 49.5 56.6\
+    Q49.5 56.6 49.5 56.2Q44.4 62.8 41.1 63Q38.5 71.2 29.4 72.2L28.9 70.9L26.4 72.6Q18.4 70 16.6 65.4\
+    Q21.1 51.1 21.1 40.1Q21.1 38.3 21 36.7Q23.7 30.5 27.9 23.4Q26.7 21.6 26.5 21.1Q26.3 20.5 26.3 20.2\
+    Q26.3 18.7 28.2 12.3Q34.1 13.9 37.2 20.8L43.1 20.8ZM27.9 36.7Q30.1 33.1 35.6 33.1L35.9 33.1\
+    Q33.4 35.7 33.4 37.8Q33.4 40.6 29.5 40.6L28.3 40.6Q27.8 40.6 27.6 38.8L27.6 38L27.9 36.7ZM27.2 61L27.6 63\
+    Q27.6 66 23.5 66.8L22.9 66.8Q21.3 66.8 21.3 65.1L21.3 63.8Q23.7 61 27.2 61Z";
+
+const WHITE_PAWN: &'static str =
+    "M50 53.1Q53.2 53.1 56.2 51.5Q59.1 49.9 60.7 47Q62.3 44.1 62.3 40.9Q62.3 37.7 60.6 34.7Q59 31.8 56.1 30.2\
+    Q53.1 28.7 50 28.7Q46.9 28.7 44 30.2Q41.1 31.8 39.4 34.7Q37.7 37.7 37.7 40.9Q37.7 44.1 39.3 47\
+    Q41 49.9 43.9 51.5Q46.8 53.1 50 53.1ZM46.6 16.3Q46.6 17.2 47 18Q47.5 18.7 48.3 19.2Q49.1 19.6 50 19.6\
+    Q50.9 19.6 51.7 19.2Q52.5 18.7 53 18Q53.4 17.2 53.4 16.3Q53.4 15.4 53 14.6Q52.5 13.8 51.7 13.3\
+    Q50.8 12.9 50 12.9Q49.2 12.9 48.3 13.3Q47.5 13.8 47 14.6Q46.6 15.4 46.6 16.3ZM81.2 92.9L81.2 93L20.1 93\
+    L20.1 92.9L15.4 93Q14.7 89.7 14.7 86.4Q14.7 77.2 19.5 68.8Q24.2 60.3 32.6 55.9Q34.8 54.7 37.1 53.8\
+    Q35.4 52.1 34.2 49.9Q31.7 45.6 31.7 40.9Q31.7 36.1 34.2 31.8Q36.6 27.5 41.1 25.1Q42.6 24.2 44.3 23.7\
+    Q42.8 22.6 41.9 20.9Q40.6 18.7 40.6 16.3Q40.6 13.8 41.9 11.6Q43.1 9.4 45.4 8.2Q47.6 7 50 7Q52.3 7 54.5 8.2\
+    Q56.8 9.4 58 11.6Q59.3 13.8 59.3 16.3Q59.3 18.7 58.1 20.9Q57.2 22.6 55.7 23.7Q57.3 24.2 59 25.1\
+    Q63.3 27.5 65.8 31.8Q68.3 36.1 68.3 40.9Q68.3 45.6 65.9 49.9Q64.6 52.1 62.9 53.8Q65.1 54.7 67.3 55.9\
+    Q75.7 60.3 80.5 68.8Q85.3 77.2 85.3 86.4Q85.3 89.7 84.6 93L81.2 92.9ZM79.3 85.4Q79.1 78.2 75.4 71.7\
+    Q71.4 64.8 64.4 61Q57.3 57.2 50 57.2Q42.6 57.2 35.5 61Q28.5 64.8 24.6 71.7Q20.9 78.2 20.7 85.4L79.3 85.4Z";
+
+const WHITE_PAWN_INSIDE: &'static str =
+    "M81.2 92.9L81.2 93L20.1 93L20.1 92.9L15.4 93Q14.7 89.7 14.7 86.4Q14.7 77.2 19.5 68.8Q24.2 60.3 32.6 55.9\
+    Q34.8 54.7 37.1 53.8Q35.4 52.1 34.2 49.9Q31.7 45.6 31.7 40.9Q31.7 36.1 34.2 31.8Q36.6 27.5 41.1 25.1\
+    Q42.6 24.2 44.3 23.7Q42.8 22.6 41.9 20.9Q40.6 18.7 40.6 16.3Q40.6 13.8 41.9 11.6Q43.1 9.4 45.4 8.2\
+    Q47.6 7 50 7Q52.3 7 54.5 8.2Q56.8 9.4 58 11.6Q59.3 13.8 59.3 16.3Q59.3 18.7 58.1 20.9Q57.2 22.6 55.7 23.7\
+    Q57.3 24.2 59 25.1Q63.3 27.5 65.8 31.8Q68.3 36.1 68.3 40.9Q68.3 45.6 65.9 49.9Q64.6 52.1 62.9 53.8\
+    Q65.1 54.7 67.3 55.9Q75.7 60.3 80.5 68.8Q85.3 77.2 85.3 86.4Q85.3 89.7 84.6 93L81.2 92.9Z";
+
+const BLACK_KING: &'static str =
+    "M46 37.4Q46 37.2 46.1 37.1Q44.1 34.6 41.2 33Q37 30.6 32.5 30.6Q28 30.6 23.8 33Q19.5 35.3 17.1 39.6\
+    Q14.7 43.8 14.7 48.4Q14.7 53.1 17.1 57.3Q19.4 61.5 23.7 63.8Q24.9 64.6 26.3 65.1L46 65.1L46 37.4ZM54.1 65.1\
+    L73.7 65.1Q75.1 64.6 76.4 63.8Q80.6 61.5 83 57.3Q85.3 53.1 85.3 48.4Q85.3 43.8 82.9 39.6Q80.5 35.3 76.3 33\
+    Q72.1 30.6 67.5 30.6Q63.1 30.6 58.9 33Q55.9 34.6 53.9 37.1Q54 37.2 54.1 37.4L54.1 65.1ZM28.3 71L28.3 88.4\
+    L71.8 88.4L71.8 71L28.3 71ZM23.6 93L23.6 71Q21.1 70.2 19 69Q15 65.9 11.9 60.2Q8.8 54.6 8.8 48.4\
+    Q8.8 42.2 11.9 36.6Q15.1 30.9 20.8 27.9Q26.5 24.8 32.5 24.8Q37.1 24.8 41.4 26.6L41.4 26.5\
+    Q41.4 24.4 42.5 22.6Q43.5 20.8 45.3 19.8Q46.1 19.4 47 19.1L47 16L40.8 16L40.8 12L47 12L47 7L53 7L53 12\
+    L59.3 12L59.3 16L53 16L53 19.1Q54 19.4 54.7 19.8Q56.5 20.8 57.6 22.6Q58.6 24.4 58.6 26.5L58.6 26.6\
+    Q63 24.8 67.5 24.8Q73.5 24.8 79.2 27.9Q84.9 30.9 88.1 36.6Q91.2 42.2 91.2 48.4Q91.2 54.6 88.1 60.2\
+    Q85 65.9 81.1 69Q78.9 70.2 76.5 71L76.5 93L23.6 93ZM44.7 26.9Q44.7 28.4 45.4 29.6Q46.1 30.9 47.4 31.6\
+    Q48.7 32.3 50 32.3Q51.4 32.3 52.7 31.6Q54 30.9 54.7 29.6Q55.3 28.4 55.3 26.9Q55.3 25.6 54.7 24.3\
+    Q54 23 52.7 22.3Q51.4 21.7 50 21.7Q48.7 21.7 47.4 22.3Q46.1 23 45.4 24.3Q44.7 25.6 44.7 26.9ZM68.8 74\
+    L68.8 85.5L31.3 85.5L31.3 74L68.8 74ZM43.1 62.1L27 62.1Q26 61.7 25 61Q21.5 59.1 19.6 55.7\
+    Q17.7 52.2 17.7 48.3Q17.7 44.5 19.6 41Q21.6 37.5 25 35.5Q28.4 33.6 32.1 33.6Q35.7 33.6 39.2 35.5\
+    Q41.5 36.8 43.2 38.9Q43.1 39.1 43.1 39.2L43.1 62.1ZM57 39.2Q57 39.1 56.8 38.9Q58.5 36.8 60.9 35.5\
+    Q64.4 33.6 67.9 33.6Q71.6 33.6 75 35.5Q78.5 37.5 80.4 41Q82.4 44.5 82.4 48.3Q82.4 52.2 80.5 55.7\
+    Q78.6 59.1 75.1 61Q74 61.7 73 62.1L57 62.1L57 39.2Z";
+
+const BLACK_QUEEN: &'static str =
+    "M16.4 34.4Q10.9 34.3 10.2 28.8Q10.2 27.2 11.1 25.8Q11.9 24.3 13.4 23.5Q15 22.7 16.7 22.7Q18.3 22.7 19.8 23.5\
+    Q21.4 24.3 22.2 25.8Q23 27.2 23 28.8Q22.8 32.5 19.5 33.8L33.2 62.9L30.2 22.5Q25.5 22.5 24.4 17.2\
+    Q24.4 15.6 25.3 14.1Q26.1 12.7 27.6 11.9Q29.1 11.1 30.9 11.1Q32.5 11.1 34 11.9Q35.6 12.7 36.4 14.1\
+    Q37.2 15.6 37.2 17.2Q37.1 19.8 34.5 21.7L45.3 64.7L47.8 17.6Q44 15.9 43.8 13.1Q43.8 11.5 44.7 10\
+    Q45.6 8.5 47.1 7.7Q48.6 7 50.2 7Q51.8 7 53.4 7.7Q54.9 8.5 55.7 10Q56.6 11.5 56.6 13.1Q56.4 16.2 52.6 17.6\
+    L54.8 64.8L65.4 22.3Q62.8 19.6 62.8 17.2Q62.8 15.6 63.6 14.2Q64.4 12.7 66 11.9Q67.5 11.1 69.1 11.1\
+    Q70.8 11.1 72.4 11.9Q73.9 12.7 74.7 14.2Q75.6 15.6 75.6 17.2Q74.9 22.1 70 23L64.7 62.9L79.5 33.3\
+    Q77 31.3 77 28.8Q77 27.2 77.9 25.8Q78.7 24.3 80.2 23.5Q81.7 22.8 83.3 22.8Q85.1 22.8 86.6 23.5\
+    Q88.1 24.3 88.9 25.8Q89.8 27.2 89.8 28.8Q89 34.2 83.3 34.2L74.1 60.9L74.1 93L24.6 93L24.6 60.9L16.4 34.4Z\
+    M70.9 72.5L70.9 68.1L29.1 68.1L29.1 72.5L70.9 72.5ZM70.9 89.7L70.9 85.1L29.1 85.1L29.1 89.7L70.9 89.7Z";
+
+const BLACK_ROOK: &'static str =
+    "M68.4 26.4L68.4 21L31.6 21L31.6 26.4L68.4 26.4ZM72.7 82.6L72.7 76.2L27.4 76.2L27.4 82.6L72.7 82.6ZM41.9 7\
+    L57.8 7L57.8 15.9L67.6 15.9L67.6 7L83.1 7L83.1 16.3L76.5 16.3L76.5 24.6L71.2 33.2L71.2 66.8L76.5 75.4\
+    L76.5 83.6L83.1 83.6L83.1 93L16.9 93L16.9 83.6L23.5 83.6L23.5 75.4L28.8 66.7L28.9 66.7L28.9 33.3L28.8 33.3\
+    L23.5 24.6L23.5 16.3L16.9 16.3L16.9 7L32.1 7L32.1 15.9L41.9 15.9L41.9 7ZM80 89.8L80 86.6L20.1 86.6L20.1 89.8\
+    L80 89.8Z";
+
+const BLACK_BISHOP: &'static str =
+    "M36.5 63.5Q36.5 60 38.4 57Q38.8 56 39.5 55.2Q36 53 34 49.4Q31.6 45.2 31.5 40.6L31.5 40.4Q31.6 35.8 34 31.6\
+    Q36.4 27.3 40.9 24.9Q42.5 24.1 44.3 23.5Q42.8 22.5 41.8 20.8Q40.5 18.6 40.5 16.2Q40.5 13.7 41.8 11.6\
+    Q43.1 9.4 45.3 8.2Q47.6 7 50 7Q52.4 7 54.6 8.2Q56.8 9.4 58.1 11.6Q59.4 13.7 59.4 16.2Q59.4 18.6 58.2 20.8\
+    Q57.3 22.5 55.8 23.6Q57.4 24.1 59.1 24.9Q63.5 27.3 66 31.6Q68.5 35.8 68.5 40.4L68.5 40.6Q68.5 45.2 66 49.4\
+    Q63.9 52.9 60.6 55.1Q61.2 56 61.7 57Q63.5 60 63.5 63.5Q63.5 66.8 61.6 69.9L77.6 93L22.4 93L38 69.8\
+    Q36.5 66.7 36.5 63.5ZM53.4 50.6L53.4 43.4L63.3 43.4L63.3 37.2L53.4 37.2L53.4 30.1L46.6 30.1L46.6 37.2\
+    L36.7 37.2L36.7 43.4L46.6 43.4L46.6 50.6L53.4 50.6Z";
+
+const BLACK_KNIGHT: &'static str =
+    "M42.6 20.9Q44.8 19.6 44.8 17.3Q44.8 15.9 43.9 14.3Q43 12.6 43 11.4Q43 8.7 46.4 7Q51.3 11.7 51.7 14.2\
+    Q52.1 16.8 52.1 17.5Q52.1 20.2 50.2 20.9L50.4 20.9Q50.4 21 50.3 21Q81.1 40.5 83.7 93L27.9 93\
+    Q27.2 89.8 27.2 86.4Q27.2 77.2 38.8 69.1Q50.5 61 50.5 54.5Q50.5 54 50.4 53.6Q46.6 63.2 39.9 63.2\
+    Q39.9 63.2 39.3 63.2Q34.1 71.6 28.2 71.6Q28 71.6 27.8 71.6L30.6 65.6L24.4 70.2Q17.9 68.6 16.3 63.9\
+    Q21.4 50.7 21.4 39.8Q21.4 38.2 21.3 36.7Q24 30.5 29.7 24.5Q29.9 23.9 29.9 23.4Q29.9 21.8 28.6 21\
+    Q27.3 20.3 27.3 18.3Q27.3 16.1 28.4 12.3Q34.4 13.9 37.6 20.9L42.6 20.9ZM27.9 35.6L27.9 36.5\
+    Q28.1 38.2 28.6 38.2L29.8 38.2Q33.7 38.2 33.7 35.4Q33.7 33.4 36.2 30.7L35.9 30.7Q30.3 30.7 28.2 34.4\
+    L27.9 35.6ZM21.6 61.4L21.6 62.8Q21.6 64.5 23.2 64.5L23.8 64.5Q27.9 63.6 27.9 60.6L27.5 58.6\
+    Q24 58.6 21.6 61.4ZM50.2 25.5L53.2 30.6Q74.8 46.1 75.1 90L79.5 90Q77 41.1 50.2 25.5Z";
+
+const BLACK_PAWN: &'static str =
+    "M15.4 93Q14.7 89.7 14.7 86.4Q14.7 77.2 19.5 68.8Q24.2 60.3 32.6 55.9Q34.8 54.7 37.1 53.8Q35.4 52.1 34.2 49.9\
+    Q31.7 45.6 31.7 40.9Q31.7 36.1 34.2 31.8Q36.6 27.5 41.1 25.1Q42.6 24.2 44.3 23.7Q42.8 22.6 41.9 20.9\
+    Q40.6 18.7 40.6 16.3Q40.6 13.8 41.9 11.6Q43.1 9.4 45.4 8.2Q47.6 7 50 7Q52.3 7 54.5 8.2Q56.8 9.4 58 11.6\
+    Q59.3 13.8 59.3 16.3Q59.3 18.7 58.1 20.9Q57.2 22.6 55.7 23.7Q57.3 24.2 59 25.1Q63.3 27.5 65.8 31.8\
+    Q68.3 36.1 68.3 40.9Q68.3 45.6 65.9 49.9Q64.6 52.1 62.9 53.8Q65.1 54.7 67.3 55.9Q75.7 60.3 80.5 68.8\
+    Q85.3 77.2 85.3 86.4Q85.3 89.7 84.6 93L15.4 93Z";
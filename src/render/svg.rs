@@ -0,0 +1,329 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! Diagrams as standalone SVG images.
+
+use std::str::FromStr;
+
+use board::{ChessGame, Coordinates, Indicator};
+use board::Color::{Black, White};
+use super::{Arrow, Highlight, MarkColor};
+use super::pieces::outline;
+
+/// Colours of an SVG diagram, as CSS colours.
+#[derive(Clone, Debug)]
+pub struct Colors {
+    pub light: String,
+    pub dark: String,
+    pub border: String,
+    pub coordinates: String,
+    pub blue: String,
+    pub green: String,
+    pub red: String,
+    pub yellow: String,
+}
+
+impl Colors {
+    /// Get the colours of a scheme.
+    pub fn from_scheme(scheme: ColorScheme) -> Self {
+        let (light, dark, border) =
+            match scheme {
+                ColorScheme::Brown => ("#f0d9b5", "#b58863", "#333333"),
+                ColorScheme::Green => ("#eeeed2", "#769656", "#333333"),
+                ColorScheme::Grayscale => ("#ffffff", "#bfbfbf", "#000000"),
+                ColorScheme::HighContrast => ("#ffffff", "#707070", "#000000"),
+            };
+        let marks =
+            match scheme {
+                ColorScheme::Brown | ColorScheme::Green => ["#3366cc", "#15781b", "#cc3333", "#e6b800"],
+                // Marks which can still be told apart when printed without colour.
+                ColorScheme::Grayscale => ["#595959", "#333333", "#000000", "#8c8c8c"],
+                ColorScheme::HighContrast => ["#0033cc", "#006600", "#cc0000", "#ff9900"],
+            };
+        Colors {
+            light: light.to_string(),
+            dark: dark.to_string(),
+            border: border.to_string(),
+            coordinates: border.to_string(),
+            blue: marks[0].to_string(),
+            green: marks[1].to_string(),
+            red: marks[2].to_string(),
+            yellow: marks[3].to_string(),
+        }
+    }
+
+    fn mark(&self, color: MarkColor) -> &str {
+        match color {
+            MarkColor::Blue => &self.blue,
+            MarkColor::Green => &self.green,
+            MarkColor::Red => &self.red,
+            MarkColor::Yellow => &self.yellow,
+        }
+    }
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Colors::from_scheme(ColorScheme::Brown)
+    }
+}
+
+/// Colour scheme of the board.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorScheme {
+    /// The classic light and dark brown.
+    Brown,
+    Green,
+    /// Shades of grey, for cheap printing.
+    Grayscale,
+    HighContrast,
+}
+
+impl FromStr for ColorScheme {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, String> {
+        match string {
+            "brown" => Ok(ColorScheme::Brown),
+            "green" => Ok(ColorScheme::Green),
+            "grayscale" => Ok(ColorScheme::Grayscale),
+            "high-contrast" => Ok(ColorScheme::HighContrast),
+            _ => Err(format!("unknown color scheme {}", string)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SvgOptions {
+    pub colors: Colors,
+    /// Where to show the file letters and the rank numbers.
+    pub coordinates: Coordinates,
+    /// How to show the side to move.
+    pub indicator: Indicator,
+    /// Size of a square, in pixels.
+    pub square_size: f32,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            colors: Colors::default(),
+            coordinates: Coordinates::Border,
+            indicator: Indicator::Dot,
+            square_size: 45.0,
+        }
+    }
+}
+
+/// Size of the square of the piece outlines.
+const OUTLINE_SIZE: f32 = 100.0;
+
+/// Render the position to a standalone SVG image.
+pub fn render(position: &ChessGame, highlights: &[Highlight], arrows: &[Arrow], options: &SvgOptions) -> String {
+    let size = options.square_size;
+    let colors = &options.colors;
+    let border = (size / 20.0).max(1.0);
+    let margin =
+        if options.coordinates == Coordinates::Border {
+            size / 2.0
+        }
+        else {
+            border
+        };
+    let right_margin =
+        match options.indicator {
+            Indicator::Arrow | Indicator::Dot => size / 2.0,
+            Indicator::None | Indicator::Text => border,
+        };
+    let text_height =
+        if options.indicator == Indicator::Text {
+            size * 0.6
+        }
+        else {
+            0.0
+        };
+    let left = margin;
+    let top = border;
+    let width = left + size * 8.0 + right_margin;
+    let height = top + size * 8.0 + margin + text_height;
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        round(width), round(height), round(width), round(height));
+    svg += &format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n", round(left - border),
+        round(top - border), round(size * 8.0 + border * 2.0), round(size * 8.0 + border * 2.0), colors.border);
+    for y in 0..8 {
+        for x in 0..8 {
+            let color =
+                if (x + y) % 2 == 0 {
+                    &colors.light
+                }
+                else {
+                    &colors.dark
+                };
+            svg += &format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                round(left + x as f32 * size), round(top + y as f32 * size), round(size), round(size), color);
+        }
+    }
+    for highlight in highlights {
+        let (x, y) = highlight.square;
+        svg += &format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"0.5\"/>\n",
+            round(left + x as f32 * size), round(top + y as f32 * size), round(size), round(size),
+            colors.mark(highlight.color));
+    }
+    for y in 0..8 {
+        for x in 0..8 {
+            if let Some(piece) = position.square(x, y) {
+                // The outlines are drawn as paths, so that the diagram does not depend on the installed fonts.
+                let outline = outline(piece);
+                let transform = format!("translate({} {}) scale({})", round(left + x as f32 * size),
+                    round(top + y as f32 * size), round_scale(size / OUTLINE_SIZE));
+                if let Some(inside) = outline.inside {
+                    svg += &format!("<path transform=\"{}\" fill=\"#ffffff\" d=\"{}\"/>\n", transform, inside);
+                }
+                svg += &format!("<path transform=\"{}\" fill=\"#000000\" d=\"{}\"/>\n", transform, outline.path);
+            }
+        }
+    }
+    for arrow in arrows {
+        svg += &format_arrow(arrow, left, top, size, colors.mark(arrow.color));
+    }
+    if options.coordinates == Coordinates::Inside {
+        svg += &format_inner_coordinates(left, top, size, colors);
+    }
+    if options.coordinates == Coordinates::Border {
+        let font_size = size * 0.3;
+        for y in 0..8 {
+            svg += &format!("<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" fill=\"{}\">{}</text>\n",
+                round(left / 2.0), round(top + (y as f32 + 0.5) * size + font_size / 3.0), round(font_size),
+                colors.coordinates, 8 - y);
+        }
+        for (x, column) in "abcdefgh".chars().enumerate() {
+            svg += &format!("<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" fill=\"{}\">{}</text>\n",
+                round(left + (x as f32 + 0.5) * size), round(top + size * 8.0 + margin / 2.0 + font_size / 3.0),
+                round(font_size), colors.coordinates, column);
+        }
+    }
+    let (y, fill) =
+        match position.turn() {
+            Black => (0.5, "#000000"),
+            White => (7.5, "#ffffff"),
+        };
+    let (x, y) = (left + size * 8.0 + right_margin / 2.0, top + y * size);
+    match options.indicator {
+        Indicator::Arrow => {
+            let radius = size / 6.0;
+            svg += &format!("<polygon points=\"{},{} {},{} {},{}\" fill=\"#000000\"/>\n", round(x - radius), round(y),
+                round(x + radius), round(y - radius), round(x + radius), round(y + radius));
+        },
+        Indicator::Dot => {
+            svg += &format!("<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"#000000\" stroke-width=\"{}\"/>\n",
+                round(x), round(y), round(size / 7.0), fill, round(size / 30.0));
+        },
+        Indicator::None => (),
+        Indicator::Text => {
+            let font_size = size * 0.35;
+            svg += &format!("<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" fill=\"{}\">{}</text>\n",
+                round(left + size * 4.0), round(height - text_height / 2.0 + font_size / 3.0), round(font_size),
+                colors.coordinates, position.turn().to_play());
+        },
+    }
+    svg += "</svg>\n";
+    svg
+}
+
+/// Format the coordinates in the corner of the squares: the ranks on the first file and the files on the first rank,
+/// in the colour of the other squares.
+fn format_inner_coordinates(left: f32, top: f32, size: f32, colors: &Colors) -> String {
+    let font_size = size * 0.22;
+    let color = |x: usize, y: usize|
+        if (x + y) % 2 == 0 {
+            &colors.dark
+        }
+        else {
+            &colors.light
+        };
+    let mut svg = String::new();
+    for y in 0..8 {
+        svg += &format!("<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" fill=\"{}\">{}</text>\n",
+            round(left + size * 0.05), round(top + y as f32 * size + font_size), round(font_size), color(0, y), 8 - y);
+    }
+    for (x, column) in "abcdefgh".chars().enumerate() {
+        svg += &format!("<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"end\" fill=\"{}\">{}</text>\n",
+            round(left + (x as f32 + 0.95) * size), round(top + size * 7.95), round(font_size), color(x, 7), column);
+    }
+    svg
+}
+
+fn format_arrow(arrow: &Arrow, left: f32, top: f32, size: f32, color: &str) -> String {
+    let center = |(x, y): (usize, usize)| (left + (x as f32 + 0.5) * size, top + (y as f32 + 0.5) * size);
+    let (x1, y1) = center(arrow.from);
+    let (x2, y2) = center(arrow.to);
+    let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+    if length == 0.0 {
+        return String::new();
+    }
+    // Unit vector along the arrow and its normal.
+    let (dx, dy) = ((x2 - x1) / length, (y2 - y1) / length);
+    let (nx, ny) = (-dy, dx);
+    let head_length = size * 0.4;
+    let head_width = size * 0.25;
+    let (base_x, base_y) = (x2 - dx * head_length, y2 - dy * head_length);
+    format!("<g fill=\"{color}\" stroke=\"{color}\" opacity=\"0.8\">\n\
+             <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke-width=\"{}\"/>\n\
+             <polygon points=\"{},{} {},{} {},{}\" stroke-width=\"0\"/>\n\
+             </g>\n",
+        round(x1), round(y1), round(base_x), round(base_y), round(size / 8.0),
+        round(x2), round(y2), round(base_x + nx * head_width), round(base_y + ny * head_width),
+        round(base_x - nx * head_width), round(base_y - ny * head_width),
+        color = color)
+}
+
+/// Round a coordinate or a size to two decimals, so that the rounding errors of the computations are not written.
+fn round(value: f32) -> f32 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Round a scale to four decimals, since it multiplies the coordinates of the outlines.
+fn round_scale(value: f32) -> f32 {
+    (value * 10000.0).round() / 10000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use board::ChessGame;
+    use super::{SvgOptions, render};
+
+    #[test]
+    fn pieces_are_paths_with_rounded_coordinates() {
+        let options = SvgOptions {
+            square_size: 13.3,
+            .. SvgOptions::default()
+        };
+        let svg = render(&ChessGame::initial(), &[], &[], &options);
+        assert_eq!(svg.matches("<path ").count(), 32 + 16);
+        assert!(!svg.contains('♔') && !svg.contains('♟'));
+        // The scales of the outlines have four decimals, the other numbers at most two.
+        for number in svg.split(|character: char| !character.is_digit(10) && character != '.') {
+            let decimals = number.find('.').map_or(0, |dot| number.len() - dot - 1);
+            assert!(decimals <= 4, "unrounded number {}", number);
+        }
+    }
+}
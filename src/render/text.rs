@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! Plain-text backend, for the terminal.
+
+use std::cmp::max;
+
+use super::{Move, Note, Sheet, Variations, unicode};
+
+/// Render the sheets of the games to plain text.
+pub fn render(sheets: &[Sheet]) -> String {
+    let games: Vec<_> = sheets.iter()
+        .map(format_sheet)
+        .collect();
+    games.join("\n\n")
+}
+
+fn format_sheet(sheet: &Sheet) -> String {
+    let moves: Vec<_> = sheet.moves.iter()
+        .map(format_move)
+        .collect();
+    let header: String = sheet.header.iter()
+        .flat_map(|header| header.lines())
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let mut result = format!("{}\n{}\n{}\n{}\n", sheet.title, header, unicode::diagram(&sheet.position),
+        moves.join(" "));
+    if let Some(ref variations) = sheet.variations {
+        result += "\n";
+        result += &format_variations(variations);
+    }
+    if !sheet.notes.is_empty() {
+        result += "\n";
+        for (index, note) in sheet.notes.iter().enumerate() {
+            result += &format!("[{}] {}\n", index + 1, format_note(note));
+        }
+    }
+    result
+}
+
+fn format_move(game_move: &Move) -> String {
+    let mut string = game_move.text.clone();
+    for note in &game_move.notes {
+        string += &format!("[{}]", note);
+    }
+    string
+}
+
+fn format_note(note: &Note) -> String {
+    match *note {
+        Note::Comment(ref comment) => comment.trim().to_string(),
+        Note::Diagram(ref comment, ref position) => {
+            let diagram: String = unicode::diagram(position).lines()
+                .map(|line| format!("\n    {}", line))
+                .collect();
+            format!("{}{}", comment.trim(), diagram)
+        },
+        Note::Moves(ref moves) => {
+            let moves: Vec<_> = moves.iter().map(format_move).collect();
+            moves.join(" ")
+        },
+    }
+}
+
+fn row_cells(moves: &[Move], columns: usize) -> Vec<String> {
+    let mut cells: Vec<_> = moves.iter().map(format_move).collect();
+    while cells.len() < columns {
+        cells.push(String::new());
+    }
+    cells
+}
+
+fn format_variations(variations: &Variations) -> String {
+    let header: Vec<_> = (variations.first_number .. variations.first_number + variations.columns)
+        .map(|number| number.to_string())
+        .collect();
+    let rows: Vec<(String, Vec<String>, Vec<String>)> = variations.lines.iter().enumerate()
+        .map(|(index, line)| ((index + 1).to_string(), row_cells(&line.white, variations.columns),
+            row_cells(&line.black, variations.columns)))
+        .collect();
+    let label_width = rows.iter()
+        .map(|&(ref label, _, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut widths: Vec<_> = header.iter().map(|number| number.chars().count()).collect();
+    for &(_, ref white, ref black) in &rows {
+        for (index, cell) in white.iter().chain(black.iter()).enumerate() {
+            let column = index % variations.columns;
+            widths[column] = max(widths[column], cell.chars().count());
+        }
+    }
+    let separator = {
+        let mut separator = format!("+{}+", "-".repeat(label_width + 2));
+        for &width in &widths {
+            separator += &format!("{}+", "-".repeat(width + 2));
+        }
+        separator + "\n"
+    };
+    let mut result = separator.clone();
+    result += &format_row("", label_width, &header, &widths);
+    result += &separator;
+    for &(ref label, ref white, ref black) in &rows {
+        result += &format_row(label, label_width, white, &widths);
+        result += &format_row("", label_width, black, &widths);
+        result += &separator;
+    }
+    result
+}
+
+fn format_row(label: &str, label_width: usize, cells: &[String], widths: &[usize]) -> String {
+    let mut row = format!("| {} |", pad(label, label_width));
+    for (cell, &width) in cells.iter().zip(widths) {
+        row += &format!(" {} |", pad(cell, width));
+    }
+    row + "\n"
+}
+
+/// Pad the text with spaces, counting the characters instead of the bytes.
+fn pad(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(text.chars().count());
+    format!("{}{}", text, " ".repeat(padding))
+}
@@ -0,0 +1,55 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! Diagrams drawn with the Unicode chess symbols and box-drawing characters.
+
+use board::{ChessGame, piece_symbol};
+use board::Color::{Black, White};
+
+/// Render the position, one rank per line, with the coordinates and the side to move.
+pub fn diagram(position: &ChessGame) -> String {
+    let mut result = "  ┌─────────────────┐\n".to_string();
+    for y in 0..8 {
+        result += &format!("{} │", 8 - y);
+        for x in 0..8 {
+            let symbol =
+                match position.square(x, y) {
+                    Some(piece) => piece_symbol(piece),
+                    None if (x + y) % 2 == 0 => ' ',
+                    None => '·',
+                };
+            result.push(' ');
+            result.push(symbol);
+        }
+        result += " │";
+        if y == 0 && position.turn() == Black {
+            result += " ●";
+        }
+        else if y == 7 && position.turn() == White {
+            result += " ○";
+        }
+        result += "\n";
+    }
+    result += "  └─────────────────┘\n";
+    result += "    a b c d e f g h\n";
+    result
+}
@@ -0,0 +1,157 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! Document templates, whose `{{name}}` variables are replaced when rendering a game.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use error::{Error, Result};
+
+const BUILTIN_TEMPLATE: &'static str = include_str!("../themes/template.adoc");
+
+enum Part {
+    Text(String),
+    Variable(String),
+}
+
+/// A parsed template.
+pub struct Template {
+    file: Option<PathBuf>,
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// Get the template embedded in the binary.
+    pub fn builtin() -> Template {
+        Template::parse(BUILTIN_TEMPLATE, None).expect("valid built-in template")
+    }
+
+    /// Read and parse the template `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Template> {
+        let path = path.as_ref();
+        let mut file = try!(File::open(path).map_err(|error| Error::io(path, error)));
+        let mut source = String::new();
+        try!(file.read_to_string(&mut source).map_err(|error| Error::io(path, error)));
+        Template::parse(&source, Some(path))
+    }
+
+    /// Parse the template `source`, read from `file`.
+    pub fn parse(source: &str, file: Option<&Path>) -> Result<Template> {
+        let file = file.map(|file| file.to_path_buf());
+        let mut parts = vec![];
+        let mut rest = source;
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                parts.push(Part::Text(rest[..start].to_string()));
+            }
+            rest = &rest[start + 2..];
+            let end =
+                match rest.find("}}") {
+                    Some(end) => end,
+                    None => return Err(Error::Template {
+                        file: file,
+                        message: "unclosed {{".to_string(),
+                    }),
+                };
+            parts.push(Part::Variable(rest[..end].trim().to_string()));
+            rest = &rest[end + 2..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Text(rest.to_string()));
+        }
+        Ok(Template {
+            file: file,
+            parts: parts,
+        })
+    }
+
+    /// Get the directory containing the template file, if any.
+    pub fn dir(&self) -> Option<&Path> {
+        self.file.as_ref().and_then(|file| file.parent())
+    }
+
+    /// Fill the template with the value of each variable given by `lookup`, which returns `None` for an unknown
+    /// variable.
+    pub fn render<F: Fn(&str) -> Option<String>>(&self, lookup: F) -> Result<String> {
+        let mut string = String::new();
+        for part in &self.parts {
+            match *part {
+                Part::Text(ref text) => string += text,
+                Part::Variable(ref name) => {
+                    match lookup(name) {
+                        Some(value) => string += &value,
+                        None => return Err(Error::Template {
+                            file: self.file.clone(),
+                            message: format!("unknown variable {}", name),
+                        }),
+                    }
+                },
+            }
+        }
+        Ok(string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use error::Error;
+    use super::Template;
+
+    #[test]
+    fn render() {
+        let template = Template::parse("== {{title}}\n{{ moves }}{{empty}}\n", None).unwrap();
+        let result = template.render(|name|
+            match name {
+                "title" => Some("Spanish".to_string()),
+                "moves" => Some("1.e4 e5".to_string()),
+                "empty" => Some(String::new()),
+                _ => None,
+            }
+        );
+        assert_eq!(result.unwrap(), "== Spanish\n1.e4 e5\n");
+    }
+
+    #[test]
+    fn unknown_variable() {
+        let template = Template::parse("== {{title}}\n{{unknown}}\n", Some(Path::new("template.adoc"))).unwrap();
+        match template.render(|name| if name == "title" { Some(String::new()) } else { None }) {
+            Err(Error::Template { file, message }) => {
+                assert_eq!(file.as_ref().map(|file| file.as_path()), Some(Path::new("template.adoc")));
+                assert_eq!(message, "unknown variable unknown");
+            },
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn unclosed_variable() {
+        match Template::parse("== {{title", None) {
+            Err(Error::Template { file: None, message }) => assert_eq!(message, "unclosed {{"),
+            Err(error) => panic!("unexpected error {}", error),
+            Ok(_) => panic!("unclosed variable parsed"),
+        }
+    }
+}
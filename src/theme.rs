@@ -0,0 +1,226 @@
+/*
+ * Copyright (c) 2016 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+/*
+ * FIMXE: change theme to remove even table row color.
+ */
+
+//! Lookup of the asciidoctor-pdf themes.
+
+use std::env;
+use std::ffi::OsString;
+use std::fs::{create_dir_all, read_dir};
+use std::path::{Path, PathBuf};
+
+use document::write_file;
+use error::{Error, Result};
+
+/// Name of the theme used when none is chosen.
+pub const DEFAULT_THEME: &'static str = "chess";
+
+const BUILTIN_THEME: &'static str = include_str!("../themes/chess-theme.yml");
+/// Chess font of the built-in theme, at the path of its font catalog.
+const BUILTIN_FONT: &'static str = "/usr/share/fonts/TTF/ChessMeridaUnicode.ttf";
+const BUILTIN_FONT_FILE: &'static str = "ChessMeridaUnicode.ttf";
+
+/// An asciidoctor-pdf theme, stored in `<dir>/<name>-theme.yml`.
+pub struct Theme {
+    /// Directory of the theme, `None` for the theme embedded in the binary.
+    pub dir: Option<PathBuf>,
+    pub name: String,
+}
+
+impl Theme {
+    /// Find the theme `name`, which is either a path to a theme file or the name of a theme in the search path:
+    /// `$PGN2PDF_THEME_PATH`, then the `pgn2pdf` directory of `$XDG_DATA_HOME` and `$XDG_DATA_DIRS`.
+    /// The default theme falls back to the built-in one.
+    pub fn find(name: &str) -> Result<Theme> {
+        if name.contains('/') || name.ends_with(".yml") {
+            return from_file(name);
+        }
+        let file_name = format!("{}-theme.yml", name);
+        for dir in search_path() {
+            if dir.join(&file_name).is_file() {
+                return Ok(Theme {
+                    dir: Some(dir),
+                    name: name.to_string(),
+                });
+            }
+        }
+        if name == DEFAULT_THEME {
+            Ok(Theme {
+                dir: None,
+                name: name.to_string(),
+            })
+        }
+        else {
+            Err(Error::Theme {
+                name: name.to_string(),
+                message: "not found in the theme search path".to_string(),
+            })
+        }
+    }
+
+    /// Get the directory of the theme, writing the built-in theme in `directory` if needed. The directory is created
+    /// if missing, and must be dedicated to the theme, whose files are overwritten.
+    /// When the chess font of the built-in theme is not at the path of its font catalog, the path is replaced by the
+    /// one found in the `fonts` directories of `$XDG_DATA_HOME` and `$XDG_DATA_DIRS`, if any.
+    pub fn install(&self, directory: &Path) -> Result<PathBuf> {
+        match self.dir {
+            Some(ref dir) => Ok(dir.clone()),
+            None => {
+                try!(create_dir_all(directory).map_err(|error| Error::io(directory, error)));
+                let theme =
+                    match find_builtin_font() {
+                        Some(ref font) if font != Path::new(BUILTIN_FONT) => {
+                            let font = format!("'{}'", font.display().to_string().replace('\'', "''"));
+                            BUILTIN_THEME.replace(BUILTIN_FONT, &font)
+                        },
+                        _ => BUILTIN_THEME.to_string(),
+                    };
+                let file = directory.join(format!("{}-theme.yml", self.name));
+                try!(write_file(&file, theme.as_bytes()));
+                Ok(directory.to_path_buf())
+            },
+        }
+    }
+
+    /// Write in `directory` a theme extending this one, installed in `theme_dir`, with the `settings` added.
+    /// Like in `install()`, the directory is created if missing and must be dedicated to the theme.
+    pub fn extend(&self, theme_dir: &Path, directory: &Path, settings: &str) -> Result<Theme> {
+        let name = "pgn2pdf-extended";
+        let base = theme_dir.join(format!("{}-theme.yml", self.name));
+        try!(create_dir_all(directory).map_err(|error| Error::io(directory, error)));
+        let content = format!("extends: '{}'\n{}", base.display().to_string().replace('\'', "''"), settings);
+        try!(write_file(&directory.join(format!("{}-theme.yml", name)), content.as_bytes()));
+        Ok(Theme {
+            dir: Some(directory.to_path_buf()),
+            name: name.to_string(),
+        })
+    }
+
+    /// Get the directory of the theme, without writing the built-in theme, which is then an error: it must be written
+    /// with `install()` first.
+    pub fn dir(&self) -> Result<PathBuf> {
+        match self.dir {
+            Some(ref dir) => Ok(dir.clone()),
+            None => Err(Error::Theme {
+                name: self.name.clone(),
+                message: "the built-in theme is not installed in the theme search path".to_string(),
+            }),
+        }
+    }
+}
+
+fn from_file(name: &str) -> Result<Theme> {
+    let path = Path::new(name);
+    if !path.is_file() {
+        return Err(Error::Theme {
+            name: name.to_string(),
+            message: "no such theme file".to_string(),
+        });
+    }
+    let stem = path.file_stem().unwrap_or("".as_ref()).to_string_lossy().into_owned();
+    if !stem.ends_with("-theme") || !name.ends_with(".yml") {
+        return Err(Error::Theme {
+            name: name.to_string(),
+            message: "the file name of a theme must end with -theme.yml".to_string(),
+        });
+    }
+    let dir =
+        match path.parent() {
+            Some(dir) if dir != Path::new("") => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+    Ok(Theme {
+        dir: Some(dir),
+        name: stem[..stem.len() - "-theme".len()].to_string(),
+    })
+}
+
+/// Get the directories where the themes are searched, by order of priority.
+fn search_path() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    if let Some(path) = non_empty_var("PGN2PDF_THEME_PATH") {
+        dirs.extend(env::split_paths(&path));
+    }
+    dirs.extend(data_dirs().iter().map(|dir| dir.join("pgn2pdf")));
+    dirs
+}
+
+/// Get `$XDG_DATA_HOME` and the directories of `$XDG_DATA_DIRS`, by order of priority.
+fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    let data_home = non_empty_var("XDG_DATA_HOME").map(PathBuf::from)
+        .or_else(|| non_empty_var("HOME").map(|home| Path::new(&home).join(".local/share")));
+    if let Some(data_home) = data_home {
+        dirs.push(data_home);
+    }
+    let data_dirs = non_empty_var("XDG_DATA_DIRS").unwrap_or_else(|| OsString::from("/usr/local/share:/usr/share"));
+    dirs.extend(env::split_paths(&data_dirs));
+    dirs
+}
+
+/// Find the chess font of the built-in theme: at the path of its font catalog, or else in the `fonts` directories of
+/// the XDG data directories.
+fn find_builtin_font() -> Option<PathBuf> {
+    let font = Path::new(BUILTIN_FONT);
+    if font.is_file() {
+        return Some(font.to_path_buf());
+    }
+    data_dirs().iter()
+        .filter_map(|dir| find_file(&dir.join("fonts"), BUILTIN_FONT_FILE))
+        .next()
+}
+
+/// Find the file `name` in `dir` or in its subdirectories.
+fn find_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    let entries =
+        match read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return None,
+        };
+    let mut subdirs = vec![];
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            subdirs.push(path);
+        }
+        else if entry.file_name() == name {
+            return Some(path);
+        }
+    }
+    subdirs.iter()
+        .filter_map(|subdir| find_file(subdir, name))
+        .next()
+}
+
+fn non_empty_var(name: &str) -> Option<OsString> {
+    env::var_os(name).and_then(|value|
+        if value.is_empty() {
+            None
+        }
+        else {
+            Some(value)
+        }
+    )
+}
@@ -20,13 +20,18 @@
  */
 
 extern crate tempdir;
+extern crate zip;
 
 use std::fs::{File, create_dir_all, remove_dir_all};
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 use std::process::{Command, Stdio};
 
-fn assert_files<P: AsRef<Path>, Q: AsRef<Path>>(expected: P, actual: Q) {
+use zip::{CompressionMethod, ZipArchive};
+
+/// Compare the file `expected` of the tests to the output `actual`, in which `tempdir` is replaced by `{tempdir}` so
+/// that the expected files do not depend on the directory of the test.
+fn assert_files<P: AsRef<Path>, Q: AsRef<Path>>(expected: P, actual: Q, tempdir: &str) {
     let expected = format!("{}/{}", std::env::current_dir().unwrap().to_str().unwrap(), expected.as_ref().to_str().unwrap());
     let mut expected_file = BufReader::new(File::open(expected).unwrap());
     let mut expected_output = vec![];
@@ -35,8 +40,8 @@ fn assert_files<P: AsRef<Path>, Q: AsRef<Path>>(expected: P, actual: Q) {
     loop {
         let size1 = expected_file.read_until(b'\n', &mut expected_output).unwrap();
         let size2 = actual_file.read_until(b'\n', &mut actual_output).unwrap();
-        assert_eq!(size1, size2);
-        if size1 == 0 || size2 == 0 {
+        assert_eq!(size1 == 0, size2 == 0, "the files do not have the same number of lines");
+        if size1 == 0 {
             break;
         }
         let tag1 = b"/CreationDate";
@@ -46,6 +51,10 @@ fn assert_files<P: AsRef<Path>, Q: AsRef<Path>>(expected: P, actual: Q) {
             actual_output.clear();
             continue
         }
+        let tag = b":pdf-stylesdir:";
+        if actual_output.starts_with(tag) {
+            actual_output = String::from_utf8(actual_output).unwrap().replace(tempdir, "{tempdir}").into_bytes();
+        }
         assert_eq!(expected_output, actual_output);
         expected_output.clear();
         actual_output.clear();
@@ -53,25 +62,30 @@ fn assert_files<P: AsRef<Path>, Q: AsRef<Path>>(expected: P, actual: Q) {
 }
 
 fn compare(input: &str) {
-    let tempdir = format!("/tmp/pgn2pdf-{}", input);
+    compare_output(input, input, "pdf", &[]);
+}
+
+/// Convert `tests/<input>.pgn` with the `args` and compare the output to `tests/<name>.<extension>`.
+fn compare_output(name: &str, input: &str, extension: &str, args: &[&str]) {
+    let tempdir = format!("/tmp/pgn2pdf-{}", name);
     create_dir_all(&tempdir).unwrap();
     let input_filename = format!("{}.pgn", input);
     let current_dir = std::env::current_dir().unwrap();
     let current_dir = current_dir.to_str().unwrap();
     let input_path = format!("{}/tests/{}", current_dir, input_filename);
-    let output_filename = format!("{}.pdf", input);
+    let output_filename = format!("{}.{}", name, extension);
     let expected_output = format!("tests/{}", output_filename);
     let output_path = format!("{}/{}", tempdir, output_filename);
     let exe = format!("{}/target/debug/pgn2pdf", current_dir);
     Command::new(exe)
         .arg(input_path)
+        .args(args)
         .arg("-o")
-        .arg(output_path)
+        .arg(&output_path)
         .stdout(Stdio::null())
         .status()
         .unwrap();
-    let actual_output = format!("{}/{}.pdf", tempdir, input);
-    assert_files(expected_output, actual_output);
+    assert_files(expected_output, output_path, &tempdir);
     remove_dir_all(tempdir).unwrap();
 }
 
@@ -82,6 +96,12 @@ macro_rules! compare {
             compare(stringify!($ident));
         }
     };
+    ($ident:ident, $input:ident, $extension:expr $(, $arg:expr)*) => {
+        #[test]
+        fn $ident() {
+            compare_output(stringify!($ident), stringify!($input), $extension, &[$($arg),*]);
+        }
+    };
 }
 
 compare!(test1);
@@ -94,3 +114,133 @@ compare!(test7);
 compare!(test8);
 compare!(test9);
 compare!(test10);
+compare!(test11, test11, "adoc", "--format=adoc");
+compare!(test11_html, test11, "html", "--format=html", "--header");
+compare!(test11_latex, test11, "tex", "--format=latex");
+compare!(test11_markdown, test11, "md", "--format=markdown");
+compare!(test11_text, test11, "txt", "--format=text");
+compare!(test11_fen, test11, "adoc", "--format=adoc", "--template=tests/fen_template.adoc");
+
+/// Convert the PGN file `name` of the tests to PDF, and return the exit code and the error output.
+fn convert_invalid(name: &str) -> (Option<i32>, String) {
+    let current_dir = std::env::current_dir().unwrap();
+    let current_dir = current_dir.to_str().unwrap();
+    let input_path = format!("{}/tests/{}.pgn", current_dir, name);
+    let exe = format!("{}/target/debug/pgn2pdf", current_dir);
+    let output = Command::new(exe)
+        .arg(input_path)
+        .arg("-o")
+        .arg(format!("/tmp/pgn2pdf-{}.pdf", name))
+        .output()
+        .unwrap();
+    (output.status.code(), String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn syntax_error() {
+    let (code, error) = convert_invalid("syntax_error");
+    assert_eq!(code, Some(2));
+    assert!(error.contains("game 2 (line 6): invalid PGN syntax"), "unexpected error: {}", error);
+}
+
+#[test]
+fn illegal_move_in_variation() {
+    let (code, error) = convert_invalid("illegal_move");
+    assert_eq!(code, Some(2));
+    assert!(error.contains("game 2: illegal move 2. Re4"), "unexpected error: {}", error);
+}
+
+#[test]
+fn empty_variation() {
+    let (code, error) = convert_invalid("empty_variation");
+    assert_eq!(code, Some(2));
+    assert!(error.contains("game 2: invalid PGN syntax (empty variation)"), "unexpected error: {}", error);
+}
+
+/// Check that the XML document `file` is well-formed: its tags are balanced and its only entities are the ones of XML.
+fn assert_well_formed(file: &str, xml: &str) {
+    let mut open_tags = vec![];
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        for (index, _) in rest[..start].match_indices('&') {
+            let entity = &rest[index..];
+            assert!(["&amp;", "&lt;", "&gt;", "&quot;", "&apos;", "&#"].iter().any(|name| entity.starts_with(name)),
+                "{}: unknown entity in {}", file, &entity[..entity.find(';').unwrap_or(0)]);
+        }
+        rest = &rest[start + 1..];
+        let end = rest.find('>').expect("unclosed tag");
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with('?') || tag.starts_with('!') || tag.ends_with('/') {
+            continue;
+        }
+        if tag.starts_with('/') {
+            assert_eq!(open_tags.pop(), Some(tag[1..].trim()), "{}: mismatched tag {}", file, tag);
+        }
+        else {
+            open_tags.push(tag.split_whitespace().next().unwrap());
+        }
+    }
+    assert!(open_tags.is_empty(), "{}: unclosed tags {:?}", file, open_tags);
+}
+
+/// Convert `tests/<input>.pgn` to EPUB with the `args` and get the name, the compression and the content of the files
+/// of the book, in the order of the archive.
+fn epub_files(name: &str, input: &str, args: &[&str]) -> Vec<(String, CompressionMethod, String)> {
+    let tempdir = format!("/tmp/pgn2pdf-{}", name);
+    create_dir_all(&tempdir).unwrap();
+    let current_dir = std::env::current_dir().unwrap();
+    let current_dir = current_dir.to_str().unwrap();
+    let exe = format!("{}/target/debug/pgn2pdf", current_dir);
+    let output_path = format!("{}/{}.epub", tempdir, name);
+    let status = Command::new(exe)
+        .arg(format!("{}/tests/{}.pgn", current_dir, input))
+        .arg("--format=epub")
+        .args(args)
+        .arg("-o")
+        .arg(&output_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let mut archive = ZipArchive::new(File::open(&output_path).unwrap()).unwrap();
+    let mut files = vec![];
+    for index in 0 .. archive.len() {
+        let mut file = archive.by_index(index).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        files.push((file.name().to_string(), file.compression(), content));
+    }
+    remove_dir_all(tempdir).unwrap();
+    files
+}
+
+#[test]
+fn epub_chapters_are_xml() {
+    let files = epub_files("epub_chapters_are_xml", "test9", &["--header"]);
+    let mut chapters = 0;
+    for &(ref name, _, ref content) in &files {
+        if name.ends_with(".xhtml") || name.ends_with(".opf") || name.ends_with(".xml") {
+            assert_well_formed(name, content);
+            if name.starts_with("OEBPS/game-") {
+                chapters += 1;
+            }
+        }
+    }
+    assert_eq!(chapters, 1);
+}
+
+#[test]
+fn epub_layout() {
+    let files = epub_files("epub_layout", "test11", &[]);
+    let names: Vec<_> = files.iter().map(|&(ref name, _, _)| name.as_str()).collect();
+    assert_eq!(names, ["mimetype", "META-INF/container.xml", "OEBPS/content.opf", "OEBPS/nav.xhtml", "OEBPS/style.css",
+        "OEBPS/game-1.xhtml"]);
+    // The mimetype must be the first file of the archive, without compression.
+    assert_eq!(files[0].1, CompressionMethod::Stored);
+    assert_eq!(files[0].2, "application/epub+zip");
+    assert!(files[1].2.contains("full-path=\"OEBPS/content.opf\""));
+    assert!(files[2].2.contains("<item id=\"game-1\" href=\"game-1.xhtml\""));
+    assert!(files[2].2.contains("<itemref idref=\"game-1\"/>"));
+    assert!(files[2].2.contains("<dc:title>Spanish - Closed and Exchange</dc:title>"));
+    assert!(files[3].2.contains("<a href=\"game-1.xhtml\">Spanish - Closed and Exchange</a>"));
+}